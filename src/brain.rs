@@ -10,8 +10,17 @@ impl Brain {
             gamma: gamma,
         }
     }
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
     pub fn learn(&self, current_value: f64, next_value: f64, reward: f64) -> f64 {
-        current_value + self.alpha * (reward + self.gamma * next_value - current_value)
+        current_value + self.alpha * self.error(current_value, next_value, reward)
+    }
+    pub fn error(&self, current_value: f64, next_value: f64, reward: f64) -> f64 {
+        self.target(next_value, reward) - current_value
+    }
+    pub fn target(&self, next_value: f64, reward: f64) -> f64 {
+        reward + self.gamma * next_value
     }
 }
 
@@ -109,4 +118,24 @@ mod tests {
             5.
         );
     }
+    #[test]
+    fn error_is_unscaled_by_alpha() {
+        assert_eq!(
+            Brain {
+                alpha: 0.5,
+                gamma: 0.5,
+            }.error(1., 2., 3.),
+            3.
+        );
+    }
+    #[test]
+    fn target_discounts_next_value() {
+        assert_eq!(
+            Brain {
+                alpha: 0.5,
+                gamma: 0.5,
+            }.target(2., 3.),
+            4.
+        );
+    }
 }
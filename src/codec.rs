@@ -0,0 +1,246 @@
+use std::io::{self, BufRead, Read, Write};
+use std::marker::PhantomData;
+
+pub trait Codec: Sized {
+    fn write_to<W: Write>(&self, &mut W) -> io::Result<()>;
+    fn read_from<R: Read>(&mut R) -> io::Result<Self>;
+}
+
+macro_rules! codec_number {
+    ($($t:ty),*) => {$(
+        impl Codec for $t {
+            fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+
+            fn read_from<R: Read>(reader: &mut R) -> io::Result<$t> {
+                let mut bytes = [0; ::std::mem::size_of::<$t>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(<$t>::from_le_bytes(bytes))
+            }
+        }
+    )*}
+}
+
+codec_number!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl Codec for usize {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u64).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<usize> {
+        Ok(u64::read_from(reader)? as usize)
+    }
+}
+
+impl Codec for isize {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as i64).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<isize> {
+        Ok(i64::read_from(reader)? as isize)
+    }
+}
+
+impl Codec for bool {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u8).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<bool> {
+        match u8::read_from(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid("bool")),
+        }
+    }
+}
+
+impl Codec for () {
+    fn write_to<W: Write>(&self, _: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn read_from<R: Read>(_: &mut R) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Codec for String {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<String> {
+        let bytes = Vec::<u8>::read_from(reader)?;
+        String::from_utf8(bytes).map_err(|_| invalid("string"))
+    }
+}
+
+impl<T: Codec> Codec for Option<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        match *self {
+            Some(ref value) => {
+                true.write_to(writer)?;
+                value.write_to(writer)
+            }
+            None => false.write_to(writer),
+        }
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<T>> {
+        match bool::read_from(reader)? {
+            true => Ok(Some(T::read_from(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T: Codec> Codec for Vec<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        for value in self {
+            value.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Vec<T>> {
+        let len = usize::read_from(reader)?;
+        (0..len).map(|_| T::read_from(reader)).collect()
+    }
+}
+
+impl<T: Codec + Copy + Default, const N: usize> Codec for [T; N] {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for value in self {
+            value.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<[T; N]> {
+        let mut values = [T::default(); N];
+        for value in values.iter_mut() {
+            *value = T::read_from(reader)?;
+        }
+        Ok(values)
+    }
+}
+
+macro_rules! codec_tuple {
+    ($($t:ident),*) => {
+        impl<$($t: Codec),*> Codec for ($($t,)*) {
+            #[allow(non_snake_case)]
+            fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                let ($(ref $t,)*) = *self;
+                $($t.write_to(writer)?;)*
+                Ok(())
+            }
+
+            fn read_from<R: Read>(reader: &mut R) -> io::Result<($($t,)*)> {
+                Ok(($($t::read_from(reader)?,)*))
+            }
+        }
+    }
+}
+
+codec_tuple!(T1, T2);
+codec_tuple!(T1, T2, T3);
+codec_tuple!(T1, T2, T3, T4);
+
+pub fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid {}", what))
+}
+
+pub struct Stream<R: BufRead, T> {
+    reader: R,
+    failed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<R: BufRead, T: Codec> Stream<R, T> {
+    pub fn new(reader: R) -> Stream<R, T> {
+        Stream {
+            reader: reader,
+            failed: false,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: BufRead, T: Codec> Iterator for Stream<R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<io::Result<T>> {
+        if self.failed {
+            return None;
+        }
+
+        match self.reader.fill_buf() {
+            Ok([]) => return None,
+            Ok(_) => {}
+            Err(error) => {
+                self.failed = true;
+                return Some(Err(error));
+            }
+        }
+
+        let value = T::read_from(&mut self.reader);
+        self.failed = value.is_err();
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+
+    fn roundtrip<T: Codec + Debug + PartialEq>(value: T) {
+        let mut bytes = vec![];
+        value.write_to(&mut bytes).unwrap();
+        assert_eq!(T::read_from(&mut &bytes[..]).unwrap(), value);
+    }
+
+    #[test]
+    fn roundtrips() {
+        roundtrip(-3i8);
+        roundtrip(1234usize);
+        roundtrip(-0.25f64);
+        roundtrip(true);
+        roundtrip("marskov".to_string());
+        roundtrip(Some(vec![1u16, 2, 3]));
+        roundtrip(None as Option<u8>);
+        roundtrip([1i8, -1, 0]);
+        roundtrip((1u8, (false, 2.5f32), -7i64));
+    }
+
+    #[test]
+    fn rejects_invalid_bool() {
+        assert!(bool::read_from(&mut &[2u8][..]).is_err());
+    }
+
+    #[test]
+    fn fails_on_truncated_input() {
+        assert!(u32::read_from(&mut &[1u8, 2][..]).is_err());
+    }
+
+    #[test]
+    fn streams_until_end() {
+        let mut bytes = vec![];
+        for value in &[1u16, 2, 3] {
+            value.write_to(&mut bytes).unwrap();
+        }
+        bytes.pop();
+
+        let mut stream = Stream::<_, u16>::new(&bytes[..]);
+        assert_eq!(stream.next().unwrap().unwrap(), 1);
+        assert_eq!(stream.next().unwrap().unwrap(), 2);
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+}
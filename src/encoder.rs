@@ -0,0 +1,4 @@
+pub trait Encoder<S, A> {
+    fn encode(&self, &S) -> Vec<f64>;
+    fn index(&self, &A) -> usize;
+}
@@ -0,0 +1,131 @@
+use super::ratios;
+use Trajectory;
+
+pub fn ordinary<S, A, T>(trajectories: &[Trajectory<S, A>], target: T, gamma: f64) -> Option<f64>
+where
+    T: Fn(&S, &A) -> Option<f64>,
+{
+    let (weights, returns) = weighted_returns(trajectories, &target, gamma)?;
+    let total: f64 = weights.iter().zip(&returns).map(|(w, g)| w * g).sum();
+    Some(total / trajectories.len() as f64)
+}
+
+pub fn weighted<S, A, T>(trajectories: &[Trajectory<S, A>], target: T, gamma: f64) -> Option<f64>
+where
+    T: Fn(&S, &A) -> Option<f64>,
+{
+    let (weights, returns) = weighted_returns(trajectories, &target, gamma)?;
+    let norm: f64 = weights.iter().sum();
+    if norm == 0. {
+        return None;
+    }
+
+    let total: f64 = weights.iter().zip(&returns).map(|(w, g)| w * g).sum();
+    Some(total / norm)
+}
+
+pub fn per_decision<S, A, T>(
+    trajectories: &[Trajectory<S, A>],
+    target: T,
+    gamma: f64,
+) -> Option<f64>
+where
+    T: Fn(&S, &A) -> Option<f64>,
+{
+    if trajectories.is_empty() {
+        return None;
+    }
+
+    let mut total = 0.;
+    for trajectory in trajectories {
+        let (mut weight, mut discount) = (1., 1.);
+        for (ratio, reward) in ratios(trajectory, &target)?.iter().zip(&trajectory.rewards) {
+            weight *= ratio;
+            total += discount * weight * reward;
+            discount *= gamma;
+        }
+    }
+    Some(total / trajectories.len() as f64)
+}
+
+fn weighted_returns<S, A, T>(
+    trajectories: &[Trajectory<S, A>],
+    target: &T,
+    gamma: f64,
+) -> Option<(Vec<f64>, Vec<f64>)>
+where
+    T: Fn(&S, &A) -> Option<f64>,
+{
+    if trajectories.is_empty() {
+        return None;
+    }
+
+    let mut weights = vec![];
+    let mut returns = vec![];
+    for trajectory in trajectories {
+        weights.push(ratios(trajectory, target)?.iter().product());
+        returns.push(
+            trajectory
+                .rewards
+                .iter()
+                .rev()
+                .fold(0., |total, reward| reward + gamma * total),
+        );
+    }
+    Some((weights, returns))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use game::counter::*;
+
+    pub fn trajectories() -> Vec<Trajectory<i8, Operation>> {
+        let mut up = Trajectory::new(0);
+        up.push(Operation::Inc, 1., 1, Some(0.5));
+        up.push(Operation::Inc, 1., 2, Some(0.5));
+
+        let mut down = Trajectory::new(0);
+        down.push(Operation::Dec, -1., -1, Some(0.5));
+        down.push(Operation::Inc, 1., 0, Some(0.5));
+
+        vec![up, down]
+    }
+
+    pub fn always_inc(_: &i8, action: &Operation) -> Option<f64> {
+        match *action {
+            Operation::Inc => Some(1.),
+            Operation::Dec => Some(0.),
+        }
+    }
+
+    #[test]
+    fn ordinary_importance_sampling() {
+        assert_eq!(ordinary(&trajectories(), always_inc, 1.), Some(4.));
+        assert_eq!(ordinary(&trajectories(), always_inc, 0.5), Some(3.));
+    }
+
+    #[test]
+    fn weighted_importance_sampling() {
+        assert_eq!(weighted(&trajectories(), always_inc, 1.), Some(2.));
+        assert_eq!(
+            weighted(&trajectories(), |_: &i8, _: &Operation| Some(0.), 1.),
+            None
+        );
+    }
+
+    #[test]
+    fn per_decision_importance_sampling() {
+        assert_eq!(per_decision(&trajectories(), always_inc, 1.), Some(3.));
+    }
+
+    #[test]
+    fn requires_behavior_probabilities() {
+        let mut trajectories = trajectories();
+        trajectories[1].probabilities[0] = None;
+
+        assert_eq!(ordinary(&trajectories, always_inc, 1.), None);
+        assert_eq!(per_decision(&trajectories, always_inc, 1.), None);
+        assert_eq!(ordinary(&[], always_inc, 1.), None);
+    }
+}
@@ -0,0 +1,52 @@
+mod importance;
+mod robust;
+
+pub use self::importance::{ordinary, per_decision, weighted};
+pub use self::robust::doubly_robust;
+
+use {Memory, Policy, Trajectory};
+
+pub fn target<'a, S, A, P, M, F>(
+    policy: &'a P,
+    memory: &'a M,
+    actions: F,
+) -> impl Fn(&S, &A) -> Option<f64> + 'a
+where
+    A: PartialEq + 'a,
+    S: 'a,
+    P: Policy<S, A>,
+    M: Memory<S, A>,
+    F: Fn(&S) -> Vec<A> + 'a,
+{
+    move |state, action| {
+        let action_values: Vec<_> = actions(state)
+            .into_iter()
+            .map(|a| {
+                let value = memory.get(state, &a);
+                (a, value)
+            })
+            .collect();
+        let probabilities = policy.probabilities(state, &action_values)?;
+
+        match action_values.iter().position(|(a, _)| a == action) {
+            Some(index) => probabilities.get(index).cloned(),
+            None => Some(0.),
+        }
+    }
+}
+
+fn ratios<S, A, T: Fn(&S, &A) -> Option<f64>>(
+    trajectory: &Trajectory<S, A>,
+    target: &T,
+) -> Option<Vec<f64>> {
+    trajectory
+        .actions
+        .iter()
+        .zip(&trajectory.states)
+        .zip(&trajectory.probabilities)
+        .map(|((action, state), behavior)| match *behavior {
+            Some(behavior) if behavior > 0. => target(state, action).map(|p| p / behavior),
+            _ => None,
+        })
+        .collect()
+}
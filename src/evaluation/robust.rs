@@ -0,0 +1,121 @@
+use super::ratios;
+use {Memory, Trajectory};
+
+pub fn doubly_robust<S, A, M, T, F>(
+    trajectories: &[Trajectory<S, A>],
+    target: T,
+    memory: &M,
+    actions: F,
+    gamma: f64,
+) -> Option<f64>
+where
+    M: Memory<S, A>,
+    T: Fn(&S, &A) -> Option<f64>,
+    F: Fn(&S) -> Vec<A>,
+{
+    if trajectories.is_empty() {
+        return None;
+    }
+
+    let mut total = 0.;
+    for trajectory in trajectories {
+        let ratios = ratios(trajectory, &target)?;
+        let mut value = 0.;
+
+        for step in (0..trajectory.len()).rev() {
+            let state = &trajectory.states[step];
+            let action = &trajectory.actions[step];
+            let mut expected = 0.;
+            for a in actions(state) {
+                expected += target(state, &a)? * memory.get(state, &a);
+            }
+
+            value = expected
+                + ratios[step]
+                    * (trajectory.rewards[step] + gamma * value - memory.get(state, action));
+        }
+        total += value;
+    }
+    Some(total / trajectories.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::importance::tests::{always_inc, trajectories};
+    use super::super::{per_decision, target};
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+    use policies::Random;
+    use Policy;
+
+    struct Unreported;
+
+    impl Policy<i8, Operation> for Unreported {
+        fn choose(&mut self, _: &i8, _: &[(Operation, f64)]) -> Option<usize> {
+            Some(0)
+        }
+    }
+
+    fn both(_: &i8) -> Vec<Operation> {
+        vec![Operation::Dec, Operation::Inc]
+    }
+
+    #[test]
+    fn matches_per_decision_without_model() {
+        let memory: Table<i8, Operation> = Table::default();
+
+        assert_eq!(
+            doubly_robust(&trajectories(), always_inc, &memory, both, 1.),
+            per_decision(&trajectories(), always_inc, 1.)
+        );
+    }
+
+    #[test]
+    fn exact_with_accurate_model() {
+        let mut memory = Table::default();
+        memory.set(0, Operation::Inc, 2.);
+        memory.set(1, Operation::Inc, 1.);
+        memory.set(-1, Operation::Inc, 1.);
+
+        assert_eq!(
+            doubly_robust(&trajectories(), always_inc, &memory, both, 1.),
+            Some(2.)
+        );
+    }
+
+    #[test]
+    fn expects_over_actions_of_each_step() {
+        let mut memory = Table::default();
+        memory.set(1, Operation::Dec, 4.);
+        let up = &trajectories()[..1];
+
+        let actions = |state: &i8| match *state {
+            1 => vec![Operation::Inc],
+            _ => both(state),
+        };
+
+        assert_eq!(
+            doubly_robust(up, target(&Random::default(), &memory, both), &memory, both, 1.),
+            Some(4.)
+        );
+        assert_eq!(
+            doubly_robust(up, target(&Random::default(), &memory, actions), &memory, actions, 1.),
+            Some(3.)
+        );
+    }
+
+    #[test]
+    fn none_without_target_probabilities() {
+        let memory: Table<i8, Operation> = Table::default();
+
+        assert_eq!(
+            doubly_robust(&trajectories(), target(&Unreported, &memory, both), &memory, both, 1.),
+            None
+        );
+        assert_eq!(
+            per_decision(&trajectories(), target(&Unreported, &memory, both), 1.),
+            None
+        );
+    }
+}
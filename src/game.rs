@@ -6,11 +6,21 @@ pub trait Game {
     fn reward(&self) -> f64;
     fn act(&mut self, &Self::Action);
     fn state(&self) -> Self::State;
+
+    fn finished(&self) -> bool {
+        false
+    }
+
+    fn legal(&self, _: &Self::State, _: &Self::Action) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 pub mod counter {
     use super::Game;
+    use codec::{invalid, Codec};
+    use std::io::{self, Read, Write};
 
     #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
     pub enum Operation {
@@ -24,6 +34,20 @@ pub mod counter {
         }
     }
 
+    impl Codec for Operation {
+        fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+            (*self == Operation::Inc).write_to(writer)
+        }
+
+        fn read_from<R: Read>(reader: &mut R) -> io::Result<Operation> {
+            match u8::read_from(reader)? {
+                0 => Ok(Operation::Dec),
+                1 => Ok(Operation::Inc),
+                _ => Err(invalid("operation")),
+            }
+        }
+    }
+
     #[derive(Clone, Debug, Default, Eq, PartialEq)]
     pub struct Counter {
         pub last_value: i8,
@@ -39,7 +63,7 @@ pub mod counter {
         }
 
         fn reward(&self) -> f64 {
-            (self.current_value - self.last_value) as f64
+            self.current_value.wrapping_sub(self.last_value) as f64
         }
 
         fn actions(&self) -> Vec<Operation> {
@@ -48,10 +72,41 @@ pub mod counter {
 
         fn act(&mut self, operation: &Operation) {
             self.last_value = self.current_value;
-            self.current_value += match *operation {
+            self.current_value = self.current_value.wrapping_add(match *operation {
                 Operation::Dec => -1,
                 Operation::Inc => 1,
-            }
+            })
+        }
+    }
+
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    pub struct Capped {
+        pub counter: Counter,
+        pub cap: i8,
+    }
+
+    impl Game for Capped {
+        type Action = Operation;
+        type State = i8;
+
+        fn state(&self) -> i8 {
+            self.counter.state()
+        }
+
+        fn reward(&self) -> f64 {
+            self.counter.reward()
+        }
+
+        fn actions(&self) -> Vec<Operation> {
+            self.counter.actions()
+        }
+
+        fn act(&mut self, operation: &Operation) {
+            self.counter.act(operation)
+        }
+
+        fn legal(&self, state: &i8, operation: &Operation) -> bool {
+            *operation == Operation::Dec || *state < self.cap
         }
     }
 }
@@ -0,0 +1,70 @@
+use Sample;
+
+pub struct Step<'a, S: 'a, A: 'a> {
+    pub sample: &'a Sample<S, A>,
+    pub epsilon: f64,
+    pub probability: Option<f64>,
+}
+
+pub struct Update<'a, S: 'a, A: 'a> {
+    pub sample: &'a Sample<S, A>,
+    pub error: f64,
+    pub delta: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Episode {
+    pub length: usize,
+    pub reward: f64,
+}
+
+pub trait Hook<S, A> {
+    fn step(&mut self, _: &Step<S, A>) {}
+    fn update(&mut self, _: &Update<S, A>) {}
+    fn episode(&mut self, _: &Episode) {}
+
+    fn probabilities(&self) -> bool {
+        false
+    }
+}
+
+impl<S, A> Hook<S, A> for () {}
+
+impl<S, A, H: Hook<S, A>> Hook<S, A> for &mut H {
+    fn step(&mut self, step: &Step<S, A>) {
+        (**self).step(step)
+    }
+
+    fn update(&mut self, update: &Update<S, A>) {
+        (**self).update(update)
+    }
+
+    fn episode(&mut self, episode: &Episode) {
+        (**self).episode(episode)
+    }
+
+    fn probabilities(&self) -> bool {
+        (**self).probabilities()
+    }
+}
+
+impl<S, A, H1: Hook<S, A>, H2: Hook<S, A>> Hook<S, A> for (H1, H2) {
+    fn step(&mut self, step: &Step<S, A>) {
+        self.0.step(step);
+        self.1.step(step);
+    }
+
+    fn update(&mut self, update: &Update<S, A>) {
+        self.0.update(update);
+        self.1.update(update);
+    }
+
+    fn episode(&mut self, episode: &Episode) {
+        self.0.episode(episode);
+        self.1.episode(episode);
+    }
+
+    fn probabilities(&self) -> bool {
+        self.0.probabilities() || self.1.probabilities()
+    }
+}
@@ -0,0 +1,369 @@
+use {Codec, Episode, Hook, Step, Update};
+use codec::invalid;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+const MAGIC: &[u8; 8] = b"marskov1";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Every {
+    Samples(usize),
+    Duration(Duration),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Progress {
+    pub steps: usize,
+    pub updates: usize,
+    pub episodes: usize,
+    pub epsilon: f64,
+}
+
+impl Progress {
+    fn samples(&self) -> usize {
+        self.steps.max(self.updates)
+    }
+}
+
+impl Codec for Progress {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (self.steps, self.updates, self.episodes, self.epsilon).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Progress> {
+        let (steps, updates, episodes, epsilon) = Codec::read_from(reader)?;
+        Ok(Progress {
+            steps: steps,
+            updates: updates,
+            episodes: episodes,
+            epsilon: epsilon,
+        })
+    }
+}
+
+pub trait Resume {
+    fn resume(&mut self, &Progress);
+}
+
+pub struct Checkpoint<M> {
+    pub progress: Progress,
+    pub memory: M,
+}
+
+impl<M: Codec> Checkpoint<M> {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Checkpoint<M>> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("checkpoint"));
+        }
+
+        Ok(Checkpoint {
+            progress: Progress::read_from(&mut reader)?,
+            memory: M::read_from(&mut reader)?,
+        })
+    }
+
+    pub fn latest<P: AsRef<Path>>(dir: P) -> io::Result<Option<Checkpoint<M>>> {
+        match checkpoints(dir.as_ref())?.pop() {
+            Some(path) => Checkpoint::load(path).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+pub struct Checkpointer<M> {
+    memory: Arc<RwLock<M>>,
+    dir: PathBuf,
+    every: Every,
+    keep: usize,
+    progress: Progress,
+    last: (usize, Instant),
+    saved: VecDeque<PathBuf>,
+    result: io::Result<()>,
+}
+
+impl<M: Codec> Checkpointer<M> {
+    pub fn new<P: AsRef<Path>>(
+        memory: Arc<RwLock<M>>,
+        dir: P,
+        every: Every,
+        keep: usize,
+    ) -> io::Result<Checkpointer<M>> {
+        assert!(keep > 0, "keep must be positive");
+        assert!(every != Every::Samples(0), "samples must be positive");
+        fs::create_dir_all(&dir)?;
+
+        Ok(Checkpointer {
+            saved: checkpoints(dir.as_ref())?.into_iter().collect(),
+            memory: memory,
+            dir: dir.as_ref().to_path_buf(),
+            every: every,
+            keep: keep,
+            progress: Progress::default(),
+            last: (0, Instant::now()),
+            result: Ok(()),
+        })
+    }
+
+    pub fn resume<P: AsRef<Path>>(
+        checkpoint: Checkpoint<M>,
+        dir: P,
+        every: Every,
+        keep: usize,
+    ) -> io::Result<Checkpointer<M>> {
+        let memory = Arc::new(RwLock::new(checkpoint.memory));
+        let mut checkpointer = Checkpointer::new(memory, dir, every, keep)?;
+
+        checkpointer.progress = checkpoint.progress;
+        checkpointer.last.0 = checkpoint.progress.samples();
+        Ok(checkpointer)
+    }
+
+    pub fn memory(&self) -> Arc<RwLock<M>> {
+        self.memory.clone()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    pub fn save(&mut self) -> io::Result<PathBuf> {
+        let name = format!("checkpoint-{:012}.bin", self.progress.samples());
+        let path = self.dir.join(name);
+        let temporary = path.with_extension("tmp");
+
+        {
+            let memory = self
+                .memory
+                .read()
+                .map_err(|_| io::Error::other("poisoned memory"))?;
+            let mut writer = BufWriter::new(File::create(&temporary)?);
+            writer.write_all(MAGIC)?;
+            self.progress.write_to(&mut writer)?;
+            memory.write_to(&mut writer)?;
+            writer.flush()?;
+        }
+        fs::rename(&temporary, &path)?;
+
+        if self.saved.back() != Some(&path) {
+            self.saved.push_back(path.clone());
+        }
+        while self.saved.len() > self.keep {
+            if let Some(old) = self.saved.pop_front() {
+                fs::remove_file(old)?;
+            }
+        }
+
+        self.last = (self.progress.samples(), Instant::now());
+        Ok(path)
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        self.result
+    }
+
+    fn tick(&mut self) {
+        let due = match self.every {
+            Every::Samples(n) => self.progress.samples() >= self.last.0 + n,
+            Every::Duration(duration) => self.last.1.elapsed() >= duration,
+        };
+
+        if due && self.result.is_ok() {
+            self.result = self.save().map(|_| ());
+        }
+    }
+}
+
+impl<S, A, M: Codec> Hook<S, A> for Checkpointer<M> {
+    fn step(&mut self, step: &Step<S, A>) {
+        self.progress.steps += 1;
+        self.progress.epsilon = step.epsilon;
+        self.tick();
+    }
+
+    fn update(&mut self, _: &Update<S, A>) {
+        self.progress.updates += 1;
+        self.tick();
+    }
+
+    fn episode(&mut self, _: &Episode) {
+        self.progress.episodes += 1;
+    }
+}
+
+fn checkpoints(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        if name.starts_with("checkpoint-") && name.ends_with(".bin") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+    use policies::{Egreedy, Random, Schedule};
+    use rand::{SeedableRng, StdRng};
+    use Policy;
+    use std::env::temp_dir;
+    use std::process;
+    use Memory;
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("marskov-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn update(checkpointer: &mut Checkpointer<Table<i8, Operation>>) {
+        let sample = (0, Operation::Inc, 1, 1.);
+        checkpointer.update(&Update {
+            sample: &sample,
+            error: 0.,
+            delta: 0.,
+        });
+    }
+
+    #[test]
+    fn keeps_last_checkpoints() {
+        let dir = dir("keeps");
+        let memory = Arc::new(RwLock::new(Table::default()));
+        let mut checkpointer = Checkpointer::new(memory.clone(), &dir, Every::Samples(2), 2).unwrap();
+
+        for value in 0..7 {
+            memory.write().unwrap().set(0, Operation::Inc, value as f64);
+            update(&mut checkpointer);
+        }
+        checkpointer.finish().unwrap();
+
+        let names: Vec<_> = checkpoints(&dir)
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["checkpoint-000000000004.bin", "checkpoint-000000000006.bin"]
+        );
+
+        let checkpoint: Checkpoint<Table<i8, Operation>> = Checkpoint::latest(&dir).unwrap().unwrap();
+        assert_eq!(checkpoint.progress.updates, 6);
+        assert_eq!(checkpoint.memory.get(&0, &Operation::Inc), 5.);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn resumes_from_checkpoint() {
+        let dir = dir("resumes");
+        let memory = Arc::new(RwLock::new(Table::default()));
+        let mut checkpointer = Checkpointer::new(memory.clone(), &dir, Every::Samples(1), 1).unwrap();
+
+        memory.write().unwrap().set(1, Operation::Dec, 2.);
+        update(&mut checkpointer);
+
+        let checkpoint: Checkpoint<Table<i8, Operation>> = Checkpoint::latest(&dir).unwrap().unwrap();
+        let mut checkpointer = Checkpointer::resume(checkpoint, &dir, Every::Samples(1), 1).unwrap();
+        assert_eq!(checkpointer.progress().updates, 1);
+        assert_eq!(
+            checkpointer.memory().read().unwrap().get(&1, &Operation::Dec),
+            2.
+        );
+
+        update(&mut checkpointer);
+        assert_eq!(checkpoints(&dir).unwrap().len(), 1);
+        assert_eq!(
+            Checkpoint::<Table<i8, Operation>>::latest(&dir)
+                .unwrap()
+                .unwrap()
+                .progress
+                .updates,
+            2
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn resumes_policy_schedule() {
+        let dir = dir("schedule");
+        let memory = Arc::new(RwLock::new(Table::<i8, Operation>::default()));
+        let mut checkpointer = Checkpointer::new(memory, &dir, Every::Samples(3), 1).unwrap();
+
+        let sample = (0, Operation::Inc, 1, 1.);
+        for _ in 0..3 {
+            checkpointer.step(&Step {
+                sample: &sample,
+                epsilon: 0.2,
+                probability: None,
+            });
+        }
+
+        let checkpoint: Checkpoint<Table<i8, Operation>> = Checkpoint::latest(&dir).unwrap().unwrap();
+        let mut schedule = Schedule::new(Random::default(), Egreedy::new(0.5), 2);
+        schedule.resume(&checkpoint.progress);
+
+        assert_eq!(schedule.count(), 3);
+        assert_eq!(Policy::<i8, Operation>::epsilon(&schedule, &0), 0.2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_policy_state_without_steps() {
+        let dir = dir("updates");
+        let memory = Arc::new(RwLock::new(Table::default()));
+        let mut checkpointer = Checkpointer::new(memory, &dir, Every::Samples(1), 1).unwrap();
+        update(&mut checkpointer);
+
+        let checkpoint: Checkpoint<Table<i8, Operation>> = Checkpoint::latest(&dir).unwrap().unwrap();
+        let mut egreedy = Egreedy::with_rng(0.3, StdRng::from_seed(&[1]));
+        egreedy.resume(&checkpoint.progress);
+        let mut schedule = Schedule::new(Random::default(), Egreedy::new(0.5), 2);
+        schedule.choose(&0, &[(Operation::Inc, 0.)]);
+        schedule.resume(&checkpoint.progress);
+
+        assert_eq!(Policy::<i8, Operation>::epsilon(&egreedy, &0), 0.3);
+        assert_eq!(schedule.count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "samples must be positive")]
+    fn rejects_zero_samples() {
+        let memory = Arc::new(RwLock::new(Table::<i8, Operation>::default()));
+        let _ = Checkpointer::new(memory, dir("every"), Every::Samples(0), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "keep must be positive")]
+    fn rejects_keeping_none() {
+        let memory = Arc::new(RwLock::new(Table::<i8, Operation>::default()));
+        let _ = Checkpointer::new(memory, dir("zero"), Every::Samples(1), 0);
+    }
+
+    #[test]
+    fn latest_is_none_without_checkpoints() {
+        let dir = dir("none");
+        assert!(Checkpoint::<Table<i8, Operation>>::latest(&dir).unwrap().is_none());
+    }
+}
@@ -0,0 +1,48 @@
+use {Episode, Hook};
+use super::window::Window;
+
+#[derive(Clone, Debug)]
+pub struct Lengths {
+    window: Window,
+}
+
+impl Lengths {
+    pub fn new(size: usize) -> Lengths {
+        Lengths {
+            window: Window::new(size),
+        }
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        self.window.average()
+    }
+}
+
+impl<S, A> Hook<S, A> for Lengths {
+    fn episode(&mut self, episode: &Episode) {
+        self.window.push(episode.length as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn averages_episode_lengths() {
+        let mut lengths = Lengths::new(3);
+
+        for length in 1..5 {
+            Hook::<i8, Operation>::episode(
+                &mut lengths,
+                &Episode {
+                    length: length,
+                    reward: 0.,
+                },
+            );
+        }
+
+        assert_eq!(lengths.average(), Some(3.));
+    }
+}
@@ -0,0 +1,259 @@
+use {Episode, Hook, Step, Update};
+use std::io::{self, Write};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Csv,
+    Jsonl,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Span {
+    steps: usize,
+    updates: usize,
+    reward: f64,
+    error: f64,
+    epsilon: f64,
+}
+
+impl Span {
+    fn step(&mut self, reward: f64, epsilon: f64) {
+        self.steps += 1;
+        self.reward += reward;
+        self.epsilon += epsilon;
+    }
+
+    fn update(&mut self, error: f64) {
+        self.updates += 1;
+        self.error += error.abs();
+    }
+
+    fn error(&self) -> Option<f64> {
+        match self.updates {
+            0 => None,
+            n => Some(self.error / n as f64),
+        }
+    }
+
+    fn epsilon(&self) -> Option<f64> {
+        match self.steps {
+            0 => None,
+            n => Some(self.epsilon / n as f64),
+        }
+    }
+}
+
+pub struct Logger<W: Write> {
+    writer: W,
+    format: Format,
+    every: usize,
+    span: Span,
+    episode: Span,
+    spans: usize,
+    episodes: usize,
+    result: io::Result<()>,
+}
+
+impl<W: Write> Logger<W> {
+    pub fn csv(writer: W, every: usize) -> Logger<W> {
+        let mut logger = Logger::new(writer, Format::Csv, every);
+        logger.result = writeln!(
+            logger.writer,
+            "kind,index,steps,updates,reward,error,epsilon"
+        );
+        logger
+    }
+
+    pub fn jsonl(writer: W, every: usize) -> Logger<W> {
+        Logger::new(writer, Format::Jsonl, every)
+    }
+
+    fn new(writer: W, format: Format, every: usize) -> Logger<W> {
+        assert!(every > 0, "every must be positive");
+        Logger {
+            writer: writer,
+            format: format,
+            every: every,
+            span: Span::default(),
+            episode: Span::default(),
+            spans: 0,
+            episodes: 0,
+            result: Ok(()),
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.tick();
+        if self.span.steps > 0 || self.span.updates > 0 {
+            self.flush();
+        }
+        self.result?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn tick(&mut self) {
+        let count = match (self.span.steps, self.span.updates) {
+            (0, n) | (n, 0) => n,
+            (steps, updates) => steps.min(updates),
+        };
+        if count >= self.every {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let span = self.span;
+        self.span = Span::default();
+        self.spans += 1;
+        self.write("steps", self.spans, &span);
+    }
+
+    fn write(&mut self, kind: &str, index: usize, span: &Span) {
+        if self.result.is_err() {
+            return;
+        }
+
+        let error = span.error();
+        let epsilon = span.epsilon();
+
+        self.result = match self.format {
+            Format::Csv => writeln!(
+                self.writer,
+                "{},{},{},{},{},{},{}",
+                kind,
+                index,
+                span.steps,
+                span.updates,
+                span.reward,
+                error.map_or(String::new(), |e| e.to_string()),
+                epsilon.map_or(String::new(), |e| e.to_string()),
+            ),
+            Format::Jsonl => writeln!(
+                self.writer,
+                "{{\"kind\":\"{}\",\"index\":{},\"steps\":{},\"updates\":{},\"reward\":{},\"error\":{},\"epsilon\":{}}}",
+                kind,
+                index,
+                span.steps,
+                span.updates,
+                json(span.reward),
+                error.map_or("null".to_string(), json),
+                epsilon.map_or("null".to_string(), json),
+            ),
+        };
+    }
+}
+
+fn json(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+impl<S, A, W: Write> Hook<S, A> for Logger<W> {
+    fn step(&mut self, step: &Step<S, A>) {
+        self.tick();
+        self.span.step(step.sample.3, step.epsilon);
+        self.episode.step(step.sample.3, step.epsilon);
+    }
+
+    fn update(&mut self, update: &Update<S, A>) {
+        self.tick();
+        self.span.update(update.error);
+        self.episode.update(update.error);
+    }
+
+    fn episode(&mut self, episode: &Episode) {
+        let mut span = self.episode;
+        span.steps = episode.length;
+        span.reward = episode.reward;
+
+        self.episode = Span::default();
+        self.episodes += 1;
+        self.write("episode", self.episodes, &span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    fn log<W: Write>(logger: &mut Logger<W>) {
+        let sample = (0, Operation::Inc, 1, 1.);
+
+        for _ in 0..3 {
+            logger.step(&Step {
+                sample: &sample,
+                epsilon: 0.5,
+                probability: Some(1.),
+            });
+            logger.update(&Update {
+                sample: &sample,
+                error: -2.,
+                delta: 1.,
+            });
+        }
+        Hook::<i8, Operation>::episode(
+            logger,
+            &Episode {
+                length: 3,
+                reward: 3.,
+            },
+        );
+    }
+
+    #[test]
+    fn writes_csv() {
+        let mut logger = Logger::csv(vec![], 2);
+        log(&mut logger);
+
+        assert_eq!(
+            String::from_utf8(logger.finish().unwrap()).unwrap(),
+            "kind,index,steps,updates,reward,error,epsilon\n\
+             steps,1,2,2,2,2,0.5\n\
+             episode,1,3,3,3,2,0.5\n\
+             steps,2,1,1,1,2,0.5\n"
+        );
+    }
+
+    #[test]
+    fn writes_jsonl() {
+        let mut logger = Logger::jsonl(vec![], 4);
+        log(&mut logger);
+
+        assert_eq!(
+            String::from_utf8(logger.finish().unwrap()).unwrap(),
+            "{\"kind\":\"episode\",\"index\":1,\"steps\":3,\"updates\":3,\
+             \"reward\":3.0,\"error\":2.0,\"epsilon\":0.5}\n\
+             {\"kind\":\"steps\",\"index\":1,\"steps\":3,\"updates\":3,\
+             \"reward\":3.0,\"error\":2.0,\"epsilon\":0.5}\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "every must be positive")]
+    fn rejects_zero_every() {
+        Logger::csv(vec![], 0);
+    }
+
+    #[test]
+    fn leaves_missing_averages_empty() {
+        let mut logger = Logger::csv(vec![], 1);
+        let sample = (0, Operation::Inc, 1, 1.);
+
+        logger.update(&Update {
+            sample: &sample,
+            error: 1.,
+            delta: 1.,
+        });
+
+        assert_eq!(
+            String::from_utf8(logger.finish().unwrap()).unwrap(),
+            "kind,index,steps,updates,reward,error,epsilon\n\
+             steps,1,0,1,0,1,\n"
+        );
+    }
+}
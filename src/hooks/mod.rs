@@ -0,0 +1,18 @@
+mod window;
+mod returns;
+mod lengths;
+mod throughput;
+mod logger;
+mod tensorboard;
+mod checkpointer;
+mod recorder;
+mod trajectories;
+
+pub use self::returns::Returns;
+pub use self::lengths::Lengths;
+pub use self::throughput::Throughput;
+pub use self::logger::Logger;
+pub use self::tensorboard::Tensorboard;
+pub use self::checkpointer::{Checkpoint, Checkpointer, Every, Progress, Resume};
+pub use self::recorder::{Recorder, Samples};
+pub use self::trajectories::Trajectories;
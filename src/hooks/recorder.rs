@@ -0,0 +1,77 @@
+use {Codec, Hook, Sample, Step, Stream};
+use std::io::{self, Write};
+
+pub struct Recorder<W: Write> {
+    writer: W,
+    samples: usize,
+    result: io::Result<()>,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(writer: W) -> Recorder<W> {
+        Recorder {
+            writer: writer,
+            samples: 0,
+            result: Ok(()),
+        }
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.result?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<S: Codec, A: Codec, W: Write> Hook<S, A> for Recorder<W> {
+    fn step(&mut self, step: &Step<S, A>) {
+        if self.result.is_ok() {
+            self.result = step.sample.write_to(&mut self.writer);
+            self.samples += 1;
+        }
+    }
+}
+
+pub type Samples<R, S, A> = Stream<R, Sample<S, A>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn reads_recorded_samples() {
+        let mut recorder = Recorder::new(vec![]);
+        let samples = vec![(0, Operation::Inc, 1, 1.), (1, Operation::Dec, 0, -1.)];
+
+        for sample in &samples {
+            recorder.step(&Step {
+                sample: sample,
+                epsilon: 0.,
+                probability: Some(1.),
+            });
+        }
+        assert_eq!(recorder.samples(), 2);
+
+        let bytes = recorder.finish().unwrap();
+        let read: Vec<Sample<i8, Operation>> = Samples::new(&bytes[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(read, samples);
+    }
+
+    #[test]
+    fn stops_after_truncated_sample() {
+        let mut bytes = vec![];
+        (0i8, Operation::Inc, 1i8, 1.).write_to(&mut bytes).unwrap();
+        bytes.pop();
+
+        let mut samples = Samples::<_, i8, Operation>::new(&bytes[..]);
+        assert!(samples.next().unwrap().is_err());
+        assert!(samples.next().is_none());
+    }
+}
@@ -0,0 +1,48 @@
+use {Episode, Hook};
+use super::window::Window;
+
+#[derive(Clone, Debug)]
+pub struct Returns {
+    window: Window,
+}
+
+impl Returns {
+    pub fn new(size: usize) -> Returns {
+        Returns {
+            window: Window::new(size),
+        }
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        self.window.average()
+    }
+}
+
+impl<S, A> Hook<S, A> for Returns {
+    fn episode(&mut self, episode: &Episode) {
+        self.window.push(episode.reward);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn averages_episode_rewards() {
+        let mut returns = Returns::new(2);
+
+        for reward in &[1., 2., 4.] {
+            Hook::<i8, Operation>::episode(
+                &mut returns,
+                &Episode {
+                    length: 1,
+                    reward: *reward,
+                },
+            );
+        }
+
+        assert_eq!(returns.average(), Some(3.));
+    }
+}
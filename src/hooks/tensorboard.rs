@@ -0,0 +1,284 @@
+use {Episode, Hook, Step, Update};
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Tensorboard<W: Write> {
+    writer: W,
+    every: usize,
+    steps: usize,
+    updates: usize,
+    played: usize,
+    trained: usize,
+    reward: f64,
+    epsilon: f64,
+    error: f64,
+    size: Option<Box<Fn() -> usize + Send>>,
+    result: io::Result<()>,
+}
+
+impl Tensorboard<BufWriter<File>> {
+    pub fn create<P: AsRef<Path>>(dir: P, every: usize) -> io::Result<Tensorboard<BufWriter<File>>> {
+        create_dir_all(&dir)?;
+        let name = format!("events.out.tfevents.{}.{}", now() as u64, process::id());
+        let file = File::create(dir.as_ref().join(name))?;
+        Tensorboard::new(BufWriter::new(file), every)
+    }
+}
+
+impl<W: Write> Tensorboard<W> {
+    pub fn new(writer: W, every: usize) -> io::Result<Tensorboard<W>> {
+        let mut event = vec![];
+        event_header(&mut event, 0);
+        field(&mut event, 3, 2);
+        bytes(&mut event, b"brain.Event:2");
+
+        let mut tensorboard = Tensorboard {
+            writer: writer,
+            every: every,
+            steps: 0,
+            updates: 0,
+            played: 0,
+            trained: 0,
+            reward: 0.,
+            epsilon: 0.,
+            error: 0.,
+            size: None,
+            result: Ok(()),
+        };
+        record(&mut tensorboard.writer, &event)?;
+        Ok(tensorboard)
+    }
+
+    pub fn with_size<F: Fn() -> usize + Send + 'static>(mut self, size: F) -> Tensorboard<W> {
+        self.size = Some(Box::new(size));
+        self
+    }
+
+    pub fn scalar(&mut self, tag: &str, step: usize, value: f64) {
+        if self.result.is_err() {
+            return;
+        }
+
+        let mut value_bytes = vec![];
+        field(&mut value_bytes, 1, 2);
+        bytes(&mut value_bytes, tag.as_bytes());
+        field(&mut value_bytes, 2, 5);
+        value_bytes.extend_from_slice(&(value as f32).to_bits().to_le_bytes());
+
+        let mut summary = vec![];
+        field(&mut summary, 1, 2);
+        bytes(&mut summary, &value_bytes);
+
+        let mut event = vec![];
+        event_header(&mut event, step);
+        field(&mut event, 5, 2);
+        bytes(&mut event, &summary);
+
+        self.result = record(&mut self.writer, &event);
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.result?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn global_step(&self) -> usize {
+        self.steps.max(self.updates)
+    }
+}
+
+impl<S, A, W: Write> Hook<S, A> for Tensorboard<W> {
+    fn step(&mut self, step: &Step<S, A>) {
+        self.steps += 1;
+        self.played += 1;
+        self.reward += step.sample.3;
+        self.epsilon += step.epsilon;
+
+        if self.played == self.every {
+            let (t, every) = (self.global_step(), self.every as f64);
+            let (reward, epsilon) = (self.reward, self.epsilon);
+            self.scalar("play/reward", t, reward / every);
+            self.scalar("play/epsilon", t, epsilon / every);
+            self.played = 0;
+            self.reward = 0.;
+            self.epsilon = 0.;
+        }
+    }
+
+    fn update(&mut self, update: &Update<S, A>) {
+        self.updates += 1;
+        self.trained += 1;
+        self.error += update.error.abs();
+
+        if self.trained == self.every {
+            let (t, error) = (self.global_step(), self.error);
+            self.scalar("train/error", t, error / self.every as f64);
+            self.trained = 0;
+            self.error = 0.;
+
+            if let Some(size) = self.size.as_ref().map(|size| size()) {
+                self.scalar("train/size", t, size as f64);
+            }
+        }
+    }
+
+    fn episode(&mut self, episode: &Episode) {
+        let t = self.global_step();
+        self.scalar("episode/return", t, episode.reward);
+        self.scalar("episode/length", t, episode.length as f64);
+    }
+}
+
+fn now() -> f64 {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9
+}
+
+fn event_header(buffer: &mut Vec<u8>, step: usize) {
+    field(buffer, 1, 1);
+    buffer.extend_from_slice(&now().to_bits().to_le_bytes());
+    field(buffer, 2, 0);
+    varint(buffer, step as u64);
+}
+
+fn field(buffer: &mut Vec<u8>, number: u64, wire: u64) {
+    varint(buffer, number << 3 | wire);
+}
+
+fn bytes(buffer: &mut Vec<u8>, data: &[u8]) {
+    varint(buffer, data.len() as u64);
+    buffer.extend_from_slice(data);
+}
+
+fn varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn record<W: Write>(writer: &mut W, data: &[u8]) -> io::Result<()> {
+    let length = (data.len() as u64).to_le_bytes();
+    writer.write_all(&length)?;
+    writer.write_all(&masked(&length).to_le_bytes())?;
+    writer.write_all(data)?;
+    writer.write_all(&masked(data).to_le_bytes())
+}
+
+fn masked(data: &[u8]) -> u32 {
+    let crc = crc32c(data);
+    (crc.rotate_right(15)).wrapping_add(0xa282_ead8)
+}
+
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82f6_3b78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    fn records(mut data: &[u8]) -> Vec<Vec<u8>> {
+        let mut records = vec![];
+        while !data.is_empty() {
+            let mut length = [0; 8];
+            length.copy_from_slice(&data[..8]);
+            let length = u64::from_le_bytes(length) as usize;
+
+            assert_eq!(&data[8..12], &masked(&data[..8]).to_le_bytes());
+            let record = &data[12..12 + length];
+            assert_eq!(
+                &data[12 + length..16 + length],
+                &masked(record).to_le_bytes()
+            );
+
+            records.push(record.to_vec());
+            data = &data[16 + length..];
+        }
+        records
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    }
+
+    #[test]
+    fn varint_encoding() {
+        let mut buffer = vec![];
+        varint(&mut buffer, 1);
+        varint(&mut buffer, 300);
+        assert_eq!(buffer, vec![0x01, 0xac, 0x02]);
+    }
+
+    #[test]
+    fn writes_version_then_scalars() {
+        let mut tensorboard = Tensorboard::new(vec![], 2).unwrap();
+        let sample = (0, Operation::Inc, 1, 1.);
+
+        for _ in 0..2 {
+            tensorboard.update(&Update {
+                sample: &sample,
+                error: 0.5,
+                delta: 0.25,
+            });
+        }
+        Hook::<i8, Operation>::episode(
+            &mut tensorboard,
+            &Episode {
+                length: 2,
+                reward: 2.,
+            },
+        );
+
+        let records = records(&tensorboard.finish().unwrap());
+        assert_eq!(records.len(), 4);
+        assert!(records[0].ends_with(b"\x1a\x0dbrain.Event:2"));
+
+        let mut error = vec![0x10, 0x02, 0x2a, 0x14, 0x0a, 0x12, 0x0a, 0x0b];
+        error.extend_from_slice(b"train/error");
+        error.push(0x15);
+        error.extend_from_slice(&0.5f32.to_bits().to_le_bytes());
+        assert!(records[1].ends_with(&error));
+    }
+
+    #[test]
+    fn writes_memory_size() {
+        let mut tensorboard = Tensorboard::new(vec![], 1).unwrap().with_size(|| 3);
+        let sample = (0, Operation::Inc, 1, 1.);
+
+        tensorboard.update(&Update {
+            sample: &sample,
+            error: 0.5,
+            delta: 0.25,
+        });
+
+        let records = records(&tensorboard.finish().unwrap());
+        assert_eq!(records.len(), 3);
+
+        let mut size = vec![0x0a, 0x0a];
+        size.extend_from_slice(b"train/size");
+        size.push(0x15);
+        size.extend_from_slice(&3f32.to_bits().to_le_bytes());
+        assert!(records[2].ends_with(&size));
+    }
+}
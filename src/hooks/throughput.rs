@@ -0,0 +1,58 @@
+use {Hook, Update};
+use std::time::Instant;
+
+#[derive(Clone, Debug)]
+pub struct Throughput {
+    start: Instant,
+    updates: usize,
+}
+
+impl Default for Throughput {
+    fn default() -> Throughput {
+        Throughput {
+            start: Instant::now(),
+            updates: 0,
+        }
+    }
+}
+
+impl Throughput {
+    pub fn updates(&self) -> usize {
+        self.updates
+    }
+
+    pub fn rate(&self) -> f64 {
+        let elapsed = self.start.elapsed();
+        let seconds = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9;
+        self.updates as f64 / seconds
+    }
+}
+
+impl<S, A> Hook<S, A> for Throughput {
+    fn update(&mut self, _: &Update<S, A>) {
+        self.updates += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn counts_updates_per_second() {
+        let mut throughput = Throughput::default();
+        let sample = (0, Operation::Inc, 1, 1.);
+
+        for _ in 0..10 {
+            throughput.update(&Update {
+                sample: &sample,
+                error: 1.,
+                delta: 0.5,
+            });
+        }
+
+        assert_eq!(throughput.updates(), 10);
+        assert!(throughput.rate() > 0.);
+    }
+}
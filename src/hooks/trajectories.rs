@@ -0,0 +1,108 @@
+use {Codec, Episode, Hook, Step, Trajectory};
+use std::io::{self, Write};
+
+pub struct Trajectories<W: Write, S, A> {
+    writer: W,
+    current: Option<Trajectory<S, A>>,
+    episodes: usize,
+    result: io::Result<()>,
+}
+
+impl<W: Write, S: Codec, A: Codec> Trajectories<W, S, A> {
+    pub fn new(writer: W) -> Trajectories<W, S, A> {
+        Trajectories {
+            writer: writer,
+            current: None,
+            episodes: 0,
+            result: Ok(()),
+        }
+    }
+
+    pub fn episodes(&self) -> usize {
+        self.episodes
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(trajectory) = self.current.take() {
+            if self.result.is_ok() {
+                self.result = trajectory.write_to(&mut self.writer);
+            }
+        }
+        self.result?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write, S: Clone + Codec, A: Clone + Codec> Hook<S, A> for Trajectories<W, S, A> {
+    fn step(&mut self, step: &Step<S, A>) {
+        let (ref state, ref action, ref next, reward) = *step.sample;
+        self.current
+            .get_or_insert_with(|| Trajectory::new(state.clone()))
+            .push(action.clone(), reward, next.clone(), step.probability);
+    }
+
+    fn episode(&mut self, _: &Episode) {
+        if let Some(trajectory) = self.current.take() {
+            if self.result.is_ok() {
+                self.result = trajectory.write_to(&mut self.writer);
+                self.episodes += 1;
+            }
+        }
+    }
+
+    fn probabilities(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use {Sample, Stream};
+
+    #[test]
+    fn writes_episodes_and_partial_trajectory() {
+        let mut trajectories = Trajectories::new(vec![]);
+        let samples: [Sample<i8, Operation>; 3] = [
+            (0, Operation::Inc, 1, 1.),
+            (1, Operation::Inc, 2, 1.),
+            (0, Operation::Dec, -1, -1.),
+        ];
+        let episode = Episode {
+            length: 2,
+            reward: 2.,
+        };
+
+        trajectories.step(&Step {
+            sample: &samples[0],
+            epsilon: 0.,
+            probability: Some(1.),
+        });
+        trajectories.step(&Step {
+            sample: &samples[1],
+            epsilon: 0.,
+            probability: None,
+        });
+        trajectories.episode(&episode);
+        trajectories.step(&Step {
+            sample: &samples[2],
+            epsilon: 0.,
+            probability: Some(1.),
+        });
+        assert_eq!(trajectories.episodes(), 1);
+
+        let bytes = trajectories.finish().unwrap();
+        let read: Vec<Trajectory<i8, Operation>> = Stream::new(&bytes[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].states, vec![0, -1]);
+        assert_eq!(read[0].states, vec![0, 1, 2]);
+        assert_eq!(read[0].actions, vec![Operation::Inc, Operation::Inc]);
+        assert_eq!(read[0].rewards, vec![1., 1.]);
+        assert_eq!(read[0].probabilities, vec![Some(1.), None]);
+    }
+}
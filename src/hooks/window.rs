@@ -0,0 +1,54 @@
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+pub struct Window {
+    values: VecDeque<f64>,
+    size: usize,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Window {
+        assert!(size > 0, "window size must be positive");
+        Window {
+            values: VecDeque::with_capacity(size),
+            size: size,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if self.values.len() == self.size {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        match self.values.len() {
+            0 => None,
+            n => Some(self.values.iter().sum::<f64>() / n as f64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_last_values() {
+        let mut window = Window::new(2);
+        assert_eq!(window.average(), None);
+
+        window.push(1.);
+        assert_eq!(window.average(), Some(1.));
+        window.push(2.);
+        window.push(4.);
+        assert_eq!(window.average(), Some(3.));
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be positive")]
+    fn rejects_zero_size() {
+        Window::new(0);
+    }
+}
@@ -3,14 +3,30 @@ extern crate rand;
 mod game;
 mod brain;
 mod sample;
+mod replay;
+mod encoder;
 mod memory;
+mod value;
+mod shared;
 mod policy;
+mod hook;
+mod codec;
+mod trajectory;
 pub mod tasks;
 pub mod memories;
 pub mod policies;
+pub mod hooks;
+pub mod evaluation;
 
 pub use self::game::Game;
 pub use self::brain::Brain;
 pub use self::sample::Sample;
+pub use self::replay::Replay;
+pub use self::encoder::Encoder;
 pub use self::memory::Memory;
+pub use self::value::ValueMemory;
+pub use self::shared::Shared;
 pub use self::policy::Policy;
+pub use self::hook::{Episode, Hook, Step, Update};
+pub use self::codec::{Codec, Stream};
+pub use self::trajectory::Trajectory;
@@ -0,0 +1,230 @@
+use Memory;
+use memories::Blend;
+
+pub trait Mapping<S> {
+    type Key;
+
+    fn key(&self, &S) -> Self::Key;
+}
+
+impl<S, K, F: Fn(&S) -> K> Mapping<S> for F {
+    type Key = K;
+
+    fn key(&self, state: &S) -> K {
+        self(state)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Grid {
+    low: Vec<f64>,
+    high: Vec<f64>,
+    bins: usize,
+}
+
+impl Grid {
+    pub fn new(low: Vec<f64>, high: Vec<f64>, bins: usize) -> Grid {
+        assert!(bins > 0, "grid bins must be positive");
+        assert_eq!(low.len(), high.len(), "grid bounds must have the same dimensions");
+        assert!(
+            low.iter().zip(&high).all(|(low, high)| high > low),
+            "grid high bounds must exceed low bounds"
+        );
+        Grid {
+            low: low,
+            high: high,
+            bins: bins,
+        }
+    }
+}
+
+impl<S: AsRef<[f64]>> Mapping<S> for Grid {
+    type Key = Vec<usize>;
+
+    fn key(&self, state: &S) -> Vec<usize> {
+        state
+            .as_ref()
+            .iter()
+            .zip(self.low.iter().zip(&self.high))
+            .map(|(x, (low, high))| {
+                let bin = ((x - low) / (high - low) * self.bins as f64).floor();
+                bin.max(0.).min((self.bins - 1) as f64) as usize
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Quantiles {
+    cuts: Vec<Vec<f64>>,
+}
+
+impl Quantiles {
+    pub fn fit<S: AsRef<[f64]>>(states: &[S], bins: usize) -> Quantiles {
+        assert!(bins > 0, "quantile bins must be positive");
+        let dimensions = states.first().map_or(0, |state| state.as_ref().len());
+        assert!(
+            states.iter().all(|state| state.as_ref().len() == dimensions),
+            "quantile states must have the same dimensions"
+        );
+
+        let cuts = (0..dimensions)
+            .map(|d| {
+                let mut values: Vec<f64> = states
+                    .iter()
+                    .map(|state| state.as_ref()[d])
+                    .filter(|value| !value.is_nan())
+                    .collect();
+                values.sort_unstable_by(|v1, v2| v1.partial_cmp(v2).unwrap());
+
+                if values.is_empty() {
+                    return vec![];
+                }
+                (1..bins)
+                    .map(|b| values[b * values.len() / bins])
+                    .collect()
+            })
+            .collect();
+
+        Quantiles { cuts: cuts }
+    }
+}
+
+impl<S: AsRef<[f64]>> Mapping<S> for Quantiles {
+    type Key = Vec<usize>;
+
+    fn key(&self, state: &S) -> Vec<usize> {
+        state
+            .as_ref()
+            .iter()
+            .zip(&self.cuts)
+            .map(|(x, cuts)| cuts.iter().take_while(|cut| *cut <= x).count())
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub struct Aggregate<F, M> {
+    mapping: F,
+    memory: M,
+}
+
+impl<F, M> Aggregate<F, M> {
+    pub fn new(mapping: F, memory: M) -> Aggregate<F, M> {
+        Aggregate {
+            mapping: mapping,
+            memory: memory,
+        }
+    }
+
+    pub fn memory(&self) -> &M {
+        &self.memory
+    }
+}
+
+impl<S, A, F: Mapping<S>, M: Memory<F::Key, A>> Memory<S, A> for Aggregate<F, M> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.memory.get(&self.mapping.key(state), action)
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        let key = self.mapping.key(&state);
+        self.memory.set(key, action, value);
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.memory.bootstrap(&self.mapping.key(state), action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.memory.raw(&self.mapping.key(state), action)
+    }
+
+    fn update<U: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: U) -> f64 {
+        let key = self.mapping.key(&state);
+        self.memory.update(key, action, f)
+    }
+}
+
+impl<F, M: Blend> Blend for Aggregate<F, M> {
+    fn blend(&mut self, other: &Aggregate<F, M>, tau: f64) {
+        self.memory.blend(&other.memory, tau);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+
+    #[test]
+    fn grid_buckets_and_clamps() {
+        let grid = Grid::new(vec![0., -1.], vec![1., 1.], 4);
+
+        assert_eq!(grid.key(&[0.1, -1.]), vec![0, 0]);
+        assert_eq!(grid.key(&[0.5, 0.9]), vec![2, 3]);
+        assert_eq!(grid.key(&[-3., 5.]), vec![0, 3]);
+    }
+
+    #[test]
+    fn quantiles_split_evenly() {
+        let states: Vec<[f64; 1]> = (0..8).map(|x| [x as f64]).collect();
+        let quantiles = Quantiles::fit(&states, 4);
+
+        assert_eq!(quantiles.key(&[0.]), vec![0]);
+        assert_eq!(quantiles.key(&[1.5]), vec![0]);
+        assert_eq!(quantiles.key(&[2.]), vec![1]);
+        assert_eq!(quantiles.key(&[7.]), vec![3]);
+        assert_eq!(quantiles.key(&[100.]), vec![3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid bins must be positive")]
+    fn grid_rejects_zero_bins() {
+        Grid::new(vec![0.], vec![1.], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid bounds must have the same dimensions")]
+    fn grid_rejects_mismatched_bounds() {
+        Grid::new(vec![0., 0.], vec![1.], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid high bounds must exceed low bounds")]
+    fn grid_rejects_empty_range() {
+        Grid::new(vec![0., 1.], vec![1., 1.], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile states must have the same dimensions")]
+    fn quantiles_reject_ragged_states() {
+        Quantiles::fit(&[vec![0., 1.], vec![2.]], 2);
+    }
+
+    #[test]
+    fn quantiles_skip_nan() {
+        let states = vec![[f64::NAN], [0.], [f64::NAN], [1.]];
+        let quantiles = Quantiles::fit(&states, 2);
+
+        assert_eq!(quantiles.key(&[0.]), vec![0]);
+        assert_eq!(quantiles.key(&[1.]), vec![1]);
+
+        let quantiles = Quantiles::fit(&[[f64::NAN]], 2);
+        assert_eq!(quantiles.key(&[1.]), vec![0]);
+    }
+
+    #[test]
+    fn shares_values_within_bucket() {
+        let table: Table<i8, Operation> = Table::default();
+        let mut aggregate = Aggregate::new(|state: &i8| state / 2, table);
+
+        aggregate.set(2, Operation::Inc, 1.);
+
+        assert_eq!(aggregate.get(&2, &Operation::Inc), 1.);
+        assert_eq!(aggregate.get(&3, &Operation::Inc), 1.);
+        assert_eq!(aggregate.get(&4, &Operation::Inc), 0.);
+        assert_eq!(aggregate.memory().get(&1, &Operation::Inc), 1.);
+    }
+}
@@ -0,0 +1,196 @@
+use Memory;
+use rand::Rng;
+use rand::distributions::{Gamma, IndependentSample, Normal};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+const DECAY: f64 = 0.99;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Posterior {
+    pub mean: f64,
+    pub precision: f64,
+    pub shape: f64,
+    pub rate: f64,
+}
+
+impl Default for Posterior {
+    fn default() -> Posterior {
+        Posterior {
+            mean: 0.,
+            precision: 1.,
+            shape: 1.,
+            rate: 1.,
+        }
+    }
+}
+
+impl Posterior {
+    pub fn observe(&self, value: f64) -> Posterior {
+        let precision = self.precision + 1.;
+        let error = value - self.mean;
+
+        Posterior {
+            mean: (self.precision * self.mean + value) / precision,
+            precision: precision,
+            shape: self.shape + 0.5,
+            rate: self.rate + self.precision * error * error / (2. * precision),
+        }
+    }
+
+    pub fn decay(&self, factor: f64) -> Posterior {
+        Posterior {
+            mean: self.mean,
+            precision: factor * self.precision,
+            shape: factor * self.shape,
+            rate: factor * self.rate,
+        }
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.shape > 1. {
+            self.rate / (self.precision * (self.shape - 1.))
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        let noise = Gamma::new(self.shape, 1. / self.rate).ind_sample(rng);
+        Normal::new(self.mean, (self.precision * noise).sqrt().recip()).ind_sample(rng)
+    }
+}
+
+// Written values are TD blends that already lean on the current mean, so old
+// evidence is decayed on every write to keep the posterior from collapsing.
+#[derive(Clone)]
+pub struct Belief<S: Eq + Hash, A> {
+    map: HashMap<S, HashMap<A, Posterior>>,
+    prior: Posterior,
+    decay: f64,
+}
+
+impl<S: Eq + Hash, A> Default for Belief<S, A> {
+    fn default() -> Belief<S, A> {
+        Belief {
+            map: HashMap::default(),
+            prior: Posterior::default(),
+            decay: DECAY,
+        }
+    }
+}
+
+impl<S: Eq + Hash, A: Eq + Hash> Belief<S, A> {
+    pub fn new(prior: Posterior) -> Belief<S, A> {
+        Belief::with_decay(prior, DECAY)
+    }
+
+    pub fn with_decay(prior: Posterior, decay: f64) -> Belief<S, A> {
+        assert!(decay > 0. && decay <= 1., "decay must be in (0, 1]");
+        Belief {
+            map: HashMap::default(),
+            prior: prior,
+            decay: decay,
+        }
+    }
+
+    pub fn posterior(&self, state: &S, action: &A) -> Posterior {
+        *self.map
+            .get(state)
+            .and_then(|map| map.get(action))
+            .unwrap_or(&self.prior)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.values().map(|map| map.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: Eq + Hash, A: Eq + Hash> Memory<S, A> for Belief<S, A> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.posterior(state, action).mean
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        let prior = self.prior;
+        let decay = self.decay;
+        let posterior = self.map
+            .entry(state)
+            .or_default()
+            .entry(action)
+            .or_insert(prior);
+        *posterior = posterior.decay(decay).observe(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+
+    #[test]
+    fn observe_updates_normal_gamma() {
+        let posterior = Posterior::default().observe(3.);
+
+        assert_eq!(
+            posterior,
+            Posterior {
+                mean: 1.5,
+                precision: 2.,
+                shape: 1.5,
+                rate: 3.25,
+            }
+        );
+        assert_eq!(posterior.variance(), 3.25);
+        assert_eq!(Posterior::default().variance(), f64::INFINITY);
+    }
+
+    #[test]
+    fn set_observes_values() {
+        let mut belief = Belief::with_decay(Posterior::default(), 1.);
+        assert!(belief.is_empty());
+
+        belief.set(0, 1, 3.);
+        belief.set(0, 1, 3.);
+
+        assert_eq!(belief.len(), 1);
+        assert_eq!(belief.get(&0, &1), 2.);
+        assert_eq!(belief.get(&1, &0), 0.);
+        assert_eq!(belief.posterior(&0, &1).precision, 3.);
+    }
+
+    #[test]
+    fn decay_bounds_precision() {
+        let mut belief = Belief::new(Posterior::default());
+        for _ in 0..10000 {
+            belief.set(0, 1, 3.);
+        }
+
+        let posterior = belief.posterior(&0, &1);
+        assert!((posterior.mean - 3.).abs() < 1e-6);
+        assert!(posterior.precision < 1. / (1. - DECAY) + 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "decay must be in (0, 1]")]
+    fn rejects_zero_decay() {
+        Belief::<i8, i8>::with_decay(Posterior::default(), 0.);
+    }
+
+    #[test]
+    fn samples_concentrate_with_observations() {
+        let mut rng = StdRng::from_seed(&[1]);
+        let mut posterior = Posterior::default();
+        for _ in 0..1000 {
+            posterior = posterior.observe(2.);
+        }
+
+        for _ in 0..10 {
+            assert!((posterior.sample(&mut rng) - 2.).abs() < 0.1);
+        }
+    }
+}
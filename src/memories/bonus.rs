@@ -0,0 +1,126 @@
+use Memory;
+use memories::Blend;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone)]
+pub struct Bonus<S: Eq + Hash, A, M> {
+    memory: M,
+    visits: HashMap<S, HashMap<A, u64>>,
+    beta: f64,
+}
+
+impl<S: Eq + Hash, A: Eq + Hash, M> Bonus<S, A, M> {
+    pub fn new(memory: M, beta: f64) -> Bonus<S, A, M> {
+        Bonus {
+            memory: memory,
+            visits: HashMap::default(),
+            beta: beta,
+        }
+    }
+
+    pub fn memory(&self) -> &M {
+        &self.memory
+    }
+
+    pub fn visits(&self, state: &S, action: &A) -> u64 {
+        *self.visits
+            .get(state)
+            .and_then(|map| map.get(action))
+            .unwrap_or(&0)
+    }
+
+    pub fn bonus(&self, state: &S, action: &A) -> f64 {
+        self.beta / ((self.visits(state, action) + 1) as f64).sqrt()
+    }
+}
+
+impl<S, A, M> Memory<S, A> for Bonus<S, A, M>
+where
+    S: Clone + Eq + Hash,
+    A: Clone + Eq + Hash,
+    M: Memory<S, A>,
+{
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.memory.get(state, action) + self.bonus(state, action)
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        *self.visits
+            .entry(state.clone())
+            .or_default()
+            .entry(action.clone())
+            .or_insert(0) += 1;
+        self.memory.set(state, action, value);
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.memory.bootstrap(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.memory.raw(state, action)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: F) -> f64 {
+        *self.visits
+            .entry(state.clone())
+            .or_default()
+            .entry(action.clone())
+            .or_insert(0) += 1;
+        self.memory.update(state, action, f)
+    }
+}
+
+impl<S: Eq + Hash, A: Eq + Hash, M: Blend> Blend for Bonus<S, A, M> {
+    fn blend(&mut self, other: &Bonus<S, A, M>, tau: f64) {
+        self.memory.blend(&other.memory, tau);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+    use tasks::{fit, Control, Limits};
+    use policies::Greedy;
+    use Brain;
+
+    #[test]
+    fn bonus_shrinks_with_visits() {
+        let mut bonus: Bonus<i8, Operation, Table<i8, Operation>> =
+            Bonus::new(Table::default(), 2.);
+
+        assert_eq!(bonus.get(&0, &Operation::Inc), 2.);
+        bonus.set(0, Operation::Inc, 1.);
+        bonus.set(0, Operation::Inc, 1.);
+        bonus.set(0, Operation::Inc, 1.);
+
+        assert_eq!(bonus.visits(&0, &Operation::Inc), 3);
+        assert_eq!(bonus.get(&0, &Operation::Inc), 2.);
+        assert_eq!(bonus.raw(&0, &Operation::Inc), 1.);
+        assert_eq!(bonus.bootstrap(&0, &Operation::Inc), 1.);
+        assert_eq!(bonus.get(&0, &Operation::Dec), 2.);
+    }
+
+    #[test]
+    fn learns_on_raw_values() {
+        let mut bonus = Bonus::new(Table::default(), 10.);
+
+        fit(
+            &Counter::default(),
+            &mut Greedy,
+            &mut bonus,
+            &[(0, Operation::Inc, 1, 4.)],
+            &Brain::new(0.5, 0.),
+            &Control::new(Limits {
+                samples: Some(2),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        assert_eq!(bonus.memory().get(&0, &Operation::Inc), 3.);
+    }
+}
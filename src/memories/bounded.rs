@@ -0,0 +1,318 @@
+use Memory;
+use std::hash::Hash;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Eviction {
+    Lru,
+    LeastVisited,
+}
+
+impl Eviction {
+    fn rank(self, visits: u64, tick: u64) -> (u64, u64) {
+        match self {
+            Eviction::Lru => (tick, 0),
+            Eviction::LeastVisited => (visits, tick),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+struct Entry {
+    value: f64,
+    visits: AtomicU64,
+    tick: AtomicU64,
+}
+
+impl Clone for Entry {
+    fn clone(&self) -> Entry {
+        Entry {
+            value: self.value,
+            visits: AtomicU64::new(self.visits.load(Ordering::Relaxed)),
+            tick: AtomicU64::new(self.tick.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Recency<S, A> {
+    order: BTreeMap<(u64, u64), (S, A)>,
+    clock: u64,
+    stats: Stats,
+}
+
+pub struct Bounded<S: Eq + Hash, A> {
+    map: HashMap<S, HashMap<A, Entry>>,
+    recency: Mutex<Recency<S, A>>,
+    capacity: usize,
+    eviction: Eviction,
+    seed: f64,
+}
+
+impl<S: Clone + Eq + Hash, A: Clone> Clone for Bounded<S, A> {
+    fn clone(&self) -> Bounded<S, A> {
+        Bounded {
+            map: self.map.clone(),
+            recency: Mutex::new(self.recency.lock().unwrap().clone()),
+            capacity: self.capacity,
+            eviction: self.eviction,
+            seed: self.seed,
+        }
+    }
+}
+
+impl<S: Eq + Hash, A> Bounded<S, A> {
+    pub fn new(capacity: usize, eviction: Eviction) -> Bounded<S, A> {
+        Bounded::with_seed(capacity, eviction, 0.)
+    }
+
+    pub fn with_seed(capacity: usize, eviction: Eviction, seed: f64) -> Bounded<S, A> {
+        assert!(capacity > 0, "capacity must be positive");
+        Bounded {
+            map: HashMap::default(),
+            recency: Mutex::new(Recency {
+                order: BTreeMap::default(),
+                clock: 0,
+                stats: Stats::default(),
+            }),
+            capacity: capacity,
+            eviction: eviction,
+            seed: seed,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.recency.lock().unwrap().order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.recency.lock().unwrap().stats
+    }
+
+    fn touch(&self, recency: &mut Recency<S, A>, entry: &Entry) {
+        let visits = entry.visits.load(Ordering::Relaxed);
+        let tick = entry.tick.load(Ordering::Relaxed);
+        recency.clock += 1;
+
+        let key = recency.order.remove(&self.eviction.rank(visits, tick)).unwrap();
+        recency
+            .order
+            .insert(self.eviction.rank(visits + 1, recency.clock), key);
+        entry.visits.store(visits + 1, Ordering::Relaxed);
+        entry.tick.store(recency.clock, Ordering::Relaxed);
+    }
+}
+
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Bounded<S, A> {
+    fn insert(&mut self, state: S, action: A, value: f64) {
+        if self.len() == self.capacity {
+            self.evict();
+        }
+
+        let recency = self.recency.get_mut().unwrap();
+        recency.clock += 1;
+        let rank = self.eviction.rank(1, recency.clock);
+        let entry = Entry {
+            value: value,
+            visits: AtomicU64::new(1),
+            tick: AtomicU64::new(recency.clock),
+        };
+
+        recency.order.insert(rank, (state.clone(), action.clone()));
+        self.map.entry(state).or_default().insert(action, entry);
+    }
+
+    fn evict(&mut self) {
+        let recency = self.recency.get_mut().unwrap();
+        let (state, action) = match recency.order.keys().next().cloned() {
+            Some(rank) => recency.order.remove(&rank).unwrap(),
+            None => return,
+        };
+        recency.stats.evictions += 1;
+
+        let empty = match self.map.get_mut(&state) {
+            Some(map) => {
+                map.remove(&action);
+                map.is_empty()
+            }
+            None => false,
+        };
+        if empty {
+            self.map.remove(&state);
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Memory<S, A> for Bounded<S, A> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        let mut recency = self.recency.lock().unwrap();
+        match self.map.get(state).and_then(|map| map.get(action)) {
+            Some(entry) => {
+                self.touch(&mut recency, entry);
+                recency.stats.hits += 1;
+                entry.value
+            }
+            None => {
+                recency.stats.misses += 1;
+                self.seed
+            }
+        }
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        if let Some(entry) = self.map.get(&state).and_then(|map| map.get(&action)) {
+            self.touch(&mut self.recency.lock().unwrap(), entry);
+        }
+        match self.map.get_mut(&state).and_then(|map| map.get_mut(&action)) {
+            Some(entry) => entry.value = value,
+            None => self.insert(state, action, value),
+        }
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: F) -> f64 {
+        if let Some(entry) = self.map.get(&state).and_then(|map| map.get(&action)) {
+            let mut recency = self.recency.lock().unwrap();
+            self.touch(&mut recency, entry);
+            recency.stats.hits += 1;
+        }
+        if let Some(entry) = self.map.get_mut(&state).and_then(|map| map.get_mut(&action)) {
+            let value = entry.value;
+            entry.value = f(value);
+            return value;
+        }
+
+        self.recency.get_mut().unwrap().stats.misses += 1;
+        let value = self.seed;
+        self.insert(state, action, f(value));
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Shared;
+    use std::sync::{Arc, RwLock};
+    use std::thread::spawn;
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut bounded = Bounded::new(2, Eviction::Lru);
+
+        bounded.set(1, 1, 1.);
+        bounded.set(2, 2, 2.);
+        bounded.get(&1, &1);
+        bounded.set(3, 3, 3.);
+
+        assert_eq!(bounded.len(), 2);
+        assert_eq!(bounded.get(&1, &1), 1.);
+        assert_eq!(bounded.get(&2, &2), 0.);
+        assert_eq!(bounded.get(&3, &3), 3.);
+    }
+
+    #[test]
+    fn least_visited_evicts_least_visited() {
+        let mut bounded = Bounded::with_seed(2, Eviction::LeastVisited, -1.);
+
+        bounded.set(1, 1, 1.);
+        bounded.get(&1, &1);
+        bounded.get(&1, &1);
+        bounded.set(2, 2, 2.);
+        bounded.get(&2, &2);
+        bounded.set(3, 3, 3.);
+
+        assert_eq!(bounded.get(&1, &1), 1.);
+        assert_eq!(bounded.get(&2, &2), -1.);
+        assert_eq!(bounded.get(&3, &3), 3.);
+    }
+
+    #[test]
+    fn updates_do_not_evict() {
+        let mut bounded = Bounded::new(1, Eviction::Lru);
+
+        bounded.set(1, 1, 1.);
+        bounded.set(1, 1, 2.);
+
+        assert_eq!(bounded.get(&1, &1), 2.);
+        assert_eq!(bounded.stats().evictions, 0);
+    }
+
+    #[test]
+    fn stats_count_hits_misses_and_evictions() {
+        let mut bounded = Bounded::new(1, Eviction::Lru);
+
+        bounded.set(1, 1, 1.);
+        bounded.set(2, 2, 2.);
+        bounded.get(&1, &1);
+        bounded.get(&2, &2);
+
+        assert_eq!(
+            bounded.stats(),
+            Stats {
+                hits: 1,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn shared_updates_touch_once() {
+        let bounded = Bounded::new(2, Eviction::LeastVisited);
+        let memory = Arc::new(RwLock::new(bounded));
+
+        memory.set(1, 1, 1.);
+        assert_eq!(memory.update(1, 1, |value| value + 1.), Some(1.));
+        memory.set(2, 2, 2.);
+        memory.get(&2, &2);
+        memory.set(3, 3, 3.);
+
+        let bounded = memory.read().unwrap();
+        assert_eq!(bounded.stats().hits, 2);
+        assert_eq!(bounded.get(&1, &1), 0.);
+        assert_eq!(bounded.get(&2, &2), 2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be positive")]
+    fn rejects_zero_capacity() {
+        Bounded::<i8, i8>::new(0, Eviction::Lru);
+    }
+
+    #[test]
+    fn shares_across_threads() {
+        let bounded: Bounded<i8, i8> = Bounded::new(4, Eviction::Lru);
+        let memory = Arc::new(RwLock::new(bounded));
+
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let memory = memory.clone();
+                spawn(move || for s in 0..8 {
+                    memory.set(s, t, 1.);
+                    memory.get(&s, &t);
+                })
+            })
+            .collect();
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(memory.read().unwrap().len(), 4);
+    }
+}
@@ -1,3 +1,19 @@
 mod table;
+mod target;
+mod bounded;
+mod sharded;
+mod network;
+mod aggregate;
+mod belief;
+mod bonus;
+mod values;
 
 pub use self::table::Table;
+pub use self::target::{Blend, Target};
+pub use self::bounded::{Bounded, Eviction, Stats};
+pub use self::sharded::Sharded;
+pub use self::network::{Activation, Layer, Network, Optimizer};
+pub use self::aggregate::{Aggregate, Grid, Mapping, Quantiles};
+pub use self::belief::{Belief, Posterior};
+pub use self::bonus::Bonus;
+pub use self::values::ValueTable;
@@ -0,0 +1,483 @@
+use {Codec, Encoder, Memory};
+use codec::invalid;
+use memories::Blend;
+use rand::{thread_rng, Rng};
+use std::io::{self, Read, Write};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activation {
+    Identity,
+    Relu,
+    Tanh,
+}
+
+impl Activation {
+    fn apply(&self, x: f64) -> f64 {
+        match *self {
+            Activation::Identity => x,
+            Activation::Relu => x.max(0.),
+            Activation::Tanh => x.tanh(),
+        }
+    }
+
+    fn derivative(&self, y: f64) -> f64 {
+        match *self {
+            Activation::Identity => 1.,
+            Activation::Relu => if y > 0. { 1. } else { 0. },
+            Activation::Tanh => 1. - y * y,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+    Sgd { rate: f64 },
+    Adam {
+        rate: f64,
+        beta1: f64,
+        beta2: f64,
+        epsilon: f64,
+    },
+}
+
+impl Optimizer {
+    pub fn sgd(rate: f64) -> Optimizer {
+        Optimizer::Sgd { rate: rate }
+    }
+
+    pub fn adam(rate: f64) -> Optimizer {
+        Optimizer::Adam {
+            rate: rate,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Layer {
+    inputs: usize,
+    outputs: usize,
+    activation: Activation,
+    params: Vec<f64>,
+    first: Vec<f64>,
+    second: Vec<f64>,
+}
+
+impl Layer {
+    pub fn new(inputs: usize, outputs: usize, activation: Activation) -> Layer {
+        Layer::with_rng(inputs, outputs, activation, &mut thread_rng())
+    }
+
+    pub fn with_rng<R: Rng>(
+        inputs: usize,
+        outputs: usize,
+        activation: Activation,
+        rng: &mut R,
+    ) -> Layer {
+        assert!(inputs > 0, "inputs must be positive");
+        let limit = (6. / (inputs + outputs) as f64).sqrt();
+        let mut params: Vec<f64> = (0..inputs * outputs)
+            .map(|_| rng.gen_range(-limit, limit))
+            .collect();
+        params.extend((0..outputs).map(|_| 0.));
+
+        Layer {
+            inputs: inputs,
+            outputs: outputs,
+            activation: activation,
+            first: vec![0.; params.len()],
+            second: vec![0.; params.len()],
+            params: params,
+        }
+    }
+
+    fn forward(&self, input: &[f64]) -> Vec<f64> {
+        let (weights, biases) = self.params.split_at(self.inputs * self.outputs);
+        weights
+            .chunks(self.inputs)
+            .zip(biases)
+            .map(|(row, bias)| {
+                let sum = row.iter().zip(input).map(|(w, x)| w * x).sum::<f64>();
+                self.activation.apply(sum + bias)
+            })
+            .collect()
+    }
+
+    fn backward(&self, input: &[f64], output: &[f64], errors: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let deltas: Vec<f64> = output
+            .iter()
+            .zip(errors)
+            .map(|(y, e)| e * self.activation.derivative(*y))
+            .collect();
+
+        let mut gradient = Vec::with_capacity(self.params.len());
+        let mut propagated = vec![0.; self.inputs];
+
+        for (o, delta) in deltas.iter().enumerate() {
+            let row = &self.params[o * self.inputs..(o + 1) * self.inputs];
+            for (i, x) in input.iter().enumerate() {
+                gradient.push(delta * x);
+                propagated[i] += delta * row[i];
+            }
+        }
+        gradient.extend(deltas);
+
+        (gradient, propagated)
+    }
+
+    fn step(&mut self, optimizer: &Optimizer, gradient: &[f64], t: u64) {
+        match *optimizer {
+            Optimizer::Sgd { rate } => for (p, g) in self.params.iter_mut().zip(gradient) {
+                *p -= rate * g;
+            },
+            Optimizer::Adam {
+                rate,
+                beta1,
+                beta2,
+                epsilon,
+            } => for (i, g) in gradient.iter().enumerate() {
+                self.first[i] = beta1 * self.first[i] + (1. - beta1) * g;
+                self.second[i] = beta2 * self.second[i] + (1. - beta2) * g * g;
+                let first = self.first[i] / (1. - beta1.powf(t as f64));
+                let second = self.second[i] / (1. - beta2.powf(t as f64));
+                self.params[i] -= rate * first / (second.sqrt() + epsilon);
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Network<E> {
+    encoder: E,
+    layers: Vec<Layer>,
+    optimizer: Optimizer,
+    delta: f64,
+    t: u64,
+}
+
+impl<E> Network<E> {
+    pub fn new(encoder: E, layers: Vec<Layer>, optimizer: Optimizer, delta: f64) -> Network<E> {
+        assert!(chained(&layers), "layer outputs must match the next layer's inputs");
+        Network {
+            encoder: encoder,
+            layers: layers,
+            optimizer: optimizer,
+            delta: delta,
+            t: 0,
+        }
+    }
+
+    fn forward(&self, input: Vec<f64>) -> Vec<Vec<f64>> {
+        let mut outputs = vec![input];
+        for layer in &self.layers {
+            let output = layer.forward(&outputs[outputs.len() - 1]);
+            outputs.push(output);
+        }
+        outputs
+    }
+}
+
+fn chained(layers: &[Layer]) -> bool {
+    layers
+        .windows(2)
+        .all(|pair| pair[0].outputs == pair[1].inputs)
+}
+
+impl<S, A, E: Encoder<S, A>> Memory<S, A> for Network<E> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        let outputs = self.forward(self.encoder.encode(state));
+        outputs[outputs.len() - 1][self.encoder.index(action)]
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        let outputs = self.forward(self.encoder.encode(&state));
+        let index = self.encoder.index(&action);
+
+        let mut errors = vec![0.; outputs[outputs.len() - 1].len()];
+        errors[index] = (outputs[outputs.len() - 1][index] - value).max(-self.delta).min(self.delta);
+
+        let mut gradients = vec![];
+        for (l, layer) in self.layers.iter().enumerate().rev() {
+            let (gradient, propagated) = layer.backward(&outputs[l], &outputs[l + 1], &errors);
+            gradients.push(gradient);
+            errors = propagated;
+        }
+
+        self.t += 1;
+        for (layer, gradient) in self.layers.iter_mut().rev().zip(gradients) {
+            layer.step(&self.optimizer, &gradient, self.t);
+        }
+    }
+}
+
+impl<E> Blend for Network<E> {
+    fn blend(&mut self, other: &Network<E>, tau: f64) {
+        for (layer, other) in self.layers.iter_mut().zip(&other.layers) {
+            for (p, q) in layer.params.iter_mut().zip(&other.params) {
+                *p = (1. - tau) * *p + tau * q;
+            }
+        }
+    }
+}
+
+impl Codec for Activation {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let tag: u8 = match *self {
+            Activation::Identity => 0,
+            Activation::Relu => 1,
+            Activation::Tanh => 2,
+        };
+        tag.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Activation> {
+        match u8::read_from(reader)? {
+            0 => Ok(Activation::Identity),
+            1 => Ok(Activation::Relu),
+            2 => Ok(Activation::Tanh),
+            _ => Err(invalid("activation")),
+        }
+    }
+}
+
+impl Codec for Optimizer {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        match *self {
+            Optimizer::Sgd { rate } => (0u8, rate).write_to(writer),
+            Optimizer::Adam {
+                rate,
+                beta1,
+                beta2,
+                epsilon,
+            } => (1u8, (rate, beta1, beta2, epsilon)).write_to(writer),
+        }
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Optimizer> {
+        match u8::read_from(reader)? {
+            0 => Ok(Optimizer::sgd(f64::read_from(reader)?)),
+            1 => {
+                let (rate, beta1, beta2, epsilon) = Codec::read_from(reader)?;
+                Ok(Optimizer::Adam {
+                    rate: rate,
+                    beta1: beta1,
+                    beta2: beta2,
+                    epsilon: epsilon,
+                })
+            }
+            _ => Err(invalid("optimizer")),
+        }
+    }
+}
+
+impl Codec for Layer {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (self.inputs, self.outputs, self.activation).write_to(writer)?;
+        self.params.write_to(writer)?;
+        self.first.write_to(writer)?;
+        self.second.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Layer> {
+        let (inputs, outputs, activation) = Codec::read_from(reader)?;
+        let (params, first, second): (Vec<f64>, Vec<f64>, Vec<f64>) = Codec::read_from(reader)?;
+        let len = inputs * outputs + outputs;
+
+        if params.len() != len || first.len() != len || second.len() != len {
+            return Err(invalid("layer"));
+        }
+
+        Ok(Layer {
+            inputs: inputs,
+            outputs: outputs,
+            activation: activation,
+            params: params,
+            first: first,
+            second: second,
+        })
+    }
+}
+
+impl<E: Codec> Codec for Network<E> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.encoder.write_to(writer)?;
+        self.layers.write_to(writer)?;
+        (self.optimizer, self.delta, self.t).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Network<E>> {
+        let encoder = E::read_from(reader)?;
+        let layers: Vec<Layer> = Codec::read_from(reader)?;
+        let (optimizer, delta, t) = Codec::read_from(reader)?;
+
+        if !chained(&layers) {
+            return Err(invalid("network"));
+        }
+
+        Ok(Network {
+            encoder: encoder,
+            layers: layers,
+            optimizer: optimizer,
+            delta: delta,
+            t: t,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, StdRng};
+    use game::counter::*;
+    use super::*;
+
+    #[derive(Clone)]
+    struct CounterEncoder;
+
+    impl Codec for CounterEncoder {
+        fn write_to<W: Write>(&self, _: &mut W) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn read_from<R: Read>(_: &mut R) -> io::Result<CounterEncoder> {
+            Ok(CounterEncoder)
+        }
+    }
+
+    impl Encoder<i8, Operation> for CounterEncoder {
+        fn encode(&self, state: &i8) -> Vec<f64> {
+            vec![*state as f64 / 10., 1.]
+        }
+
+        fn index(&self, operation: &Operation) -> usize {
+            match *operation {
+                Operation::Dec => 0,
+                Operation::Inc => 1,
+            }
+        }
+    }
+
+    fn network(optimizer: Optimizer) -> Network<CounterEncoder> {
+        let mut rng = StdRng::from_seed(&[1]);
+        Network::new(
+            CounterEncoder,
+            vec![
+                Layer::with_rng(2, 8, Activation::Tanh, &mut rng),
+                Layer::with_rng(8, 8, Activation::Relu, &mut rng),
+                Layer::with_rng(8, 2, Activation::Identity, &mut rng),
+            ],
+            optimizer,
+            1.,
+        )
+    }
+
+    fn fit(mut network: Network<CounterEncoder>) {
+        for _ in 0..2000 {
+            for state in -5..6 {
+                network.set(state, Operation::Inc, state as f64 / 10.);
+                network.set(state, Operation::Dec, -0.5);
+            }
+        }
+
+        for state in -5..6 {
+            assert!((network.get(&state, &Operation::Inc) - state as f64 / 10.).abs() < 0.05);
+            assert!((network.get(&state, &Operation::Dec) + 0.5).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn activations() {
+        assert_eq!(Activation::Identity.apply(-2.), -2.);
+        assert_eq!(Activation::Relu.apply(-2.), 0.);
+        assert_eq!(Activation::Relu.apply(2.), 2.);
+        assert_eq!(Activation::Tanh.apply(0.), 0.);
+        assert_eq!(Activation::Relu.derivative(0.), 0.);
+        assert_eq!(Activation::Tanh.derivative(0.), 1.);
+    }
+
+    #[test]
+    fn set_moves_value_toward_target() {
+        let mut network = network(Optimizer::sgd(0.1));
+        let dec = network.get(&0, &Operation::Dec);
+
+        network.set(0, Operation::Dec, 10.);
+        assert!(network.get(&0, &Operation::Dec) > dec);
+    }
+
+    #[test]
+    fn fits_with_sgd() {
+        fit(network(Optimizer::sgd(0.05)));
+    }
+
+    #[test]
+    fn fits_with_adam() {
+        fit(network(Optimizer::adam(0.005)));
+    }
+
+    #[test]
+    fn blend_one_copies_other() {
+        let mut network1 = network(Optimizer::sgd(0.1));
+        let mut network2 = network1.clone();
+
+        network2.set(0, Operation::Dec, 10.);
+        assert!(network1.get(&0, &Operation::Dec) != network2.get(&0, &Operation::Dec));
+
+        network1.blend(&network2, 1.);
+        assert_eq!(
+            network1.get(&0, &Operation::Dec),
+            network2.get(&0, &Operation::Dec)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "inputs must be positive")]
+    fn rejects_zero_inputs() {
+        Layer::new(0, 2, Activation::Identity);
+    }
+
+    #[test]
+    #[should_panic(expected = "layer outputs must match the next layer's inputs")]
+    fn rejects_mismatched_layers() {
+        Network::new(
+            CounterEncoder,
+            vec![
+                Layer::new(2, 8, Activation::Tanh),
+                Layer::new(4, 2, Activation::Identity),
+            ],
+            Optimizer::sgd(0.1),
+            1.,
+        );
+    }
+
+    #[test]
+    fn adam_survives_long_training() {
+        let mut network = network(Optimizer::adam(0.01));
+        network.t = i32::MAX as u64;
+
+        network.set(0, Operation::Dec, 1.);
+        assert!(network.get(&0, &Operation::Dec).is_finite());
+    }
+
+    #[test]
+    fn codec_roundtrip() {
+        let mut network = network(Optimizer::adam(0.01));
+        network.set(1, Operation::Inc, 1.);
+
+        let mut bytes = vec![];
+        network.write_to(&mut bytes).unwrap();
+        let mut decoded: Network<CounterEncoder> = Network::read_from(&mut &bytes[..]).unwrap();
+
+        assert_eq!(
+            decoded.get(&1, &Operation::Inc),
+            network.get(&1, &Operation::Inc)
+        );
+
+        network.set(1, Operation::Inc, 1.);
+        decoded.set(1, Operation::Inc, 1.);
+        assert_eq!(
+            decoded.get(&1, &Operation::Inc),
+            network.get(&1, &Operation::Inc)
+        );
+    }
+}
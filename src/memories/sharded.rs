@@ -0,0 +1,120 @@
+use {Memory, Shared};
+use memories::Table;
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use std::sync::RwLock;
+
+pub struct Sharded<S: Eq + Hash, A> {
+    shards: Vec<RwLock<Table<S, A>>>,
+}
+
+impl<S: Eq + Hash, A> Sharded<S, A> {
+    pub fn new(shards: usize) -> Sharded<S, A> {
+        Sharded::with_seed(shards, 0.)
+    }
+
+    pub fn with_seed(shards: usize, seed: f64) -> Sharded<S, A> {
+        assert!(shards > 0, "shard count must be positive");
+        Sharded {
+            shards: (0..shards)
+                .map(|_| RwLock::new(Table::with_seed(seed)))
+                .collect(),
+        }
+    }
+
+    fn shard(&self, state: &S) -> &RwLock<Table<S, A>> {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Shared<S, A> for Sharded<S, A> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        Shared::get(self.shard(state), state, action)
+    }
+
+    fn set(&self, state: S, action: A, value: f64) {
+        Shared::set(self.shard(&state), state, action, value)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&self, state: S, action: A, f: F) -> Option<f64> {
+        Shared::update(self.shard(&state), state, action, f)
+    }
+}
+
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Memory<S, A> for Sharded<S, A> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        Shared::get(self, state, action)
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        Shared::set(self, state, action, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread::spawn;
+
+    #[test]
+    fn seed_get_set_get() {
+        let sharded = Sharded::with_seed(4, 1.3);
+
+        assert_eq!(Shared::get(&sharded, &2, &4), 1.3);
+        Shared::set(&sharded, 2, 4, 3.1);
+        assert_eq!(Shared::get(&sharded, &2, &4), 3.1);
+        assert_eq!(Shared::get(&sharded, &4, &2), 1.3);
+    }
+
+    #[test]
+    #[should_panic(expected = "shard count must be positive")]
+    fn rejects_zero_shards() {
+        Sharded::<i8, i8>::new(0);
+    }
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let sharded = Arc::new(Sharded::new(2));
+
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let sharded = sharded.clone();
+                spawn(move || for _ in 0..1000 {
+                    sharded.update(0, 0, |value| value + 1.);
+                })
+            })
+            .collect();
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(Shared::get(&*sharded, &0, &0), 4000.);
+    }
+
+    #[test]
+    fn concurrent_writers() {
+        let sharded = Arc::new(Sharded::new(8));
+
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let sharded = sharded.clone();
+                spawn(move || for s in 0..100 {
+                    Shared::set(&*sharded, s, t, (s * t) as f64);
+                })
+            })
+            .collect();
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        for t in 0..4 {
+            for s in 0..100 {
+                assert_eq!(Memory::get(&*sharded, &s, &t), (s * t) as f64);
+            }
+        }
+    }
+}
@@ -1,47 +1,218 @@
-use Memory;
+use {Codec, Memory};
+use codec::invalid;
+use memories::Blend;
 use std::hash::Hash;
 use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard};
+
+type Initializer<S, A> = Arc<Fn(&S, &A) -> f64 + Send + Sync>;
+
+#[derive(Clone, Default)]
+struct Entries<S: Eq + Hash, A> {
+    map: HashMap<S, HashMap<A, f64>>,
+    len: usize,
+}
+
+impl<S: Eq + Hash, A: Eq + Hash> Entries<S, A> {
+    fn get(&self, state: &S, action: &A) -> Option<f64> {
+        self.map.get(state).and_then(|map| map.get(action)).cloned()
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        if self.map.entry(state).or_default().insert(action, value).is_none() {
+            self.len += 1;
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct Table<S: Eq + Hash, A> {
-    map: HashMap<S, HashMap<A, f64>>,
+    entries: RwLock<Entries<S, A>>,
     seed: f64,
+    initializer: Option<Initializer<S, A>>,
+    insert: bool,
+}
+
+impl<S: Clone + Eq + Hash, A: Clone> Clone for Table<S, A> {
+    fn clone(&self) -> Table<S, A> {
+        Table {
+            entries: RwLock::new(self.entries().clone()),
+            seed: self.seed,
+            initializer: self.initializer.clone(),
+            insert: self.insert,
+        }
+    }
 }
 
 impl<S: Eq + Hash, A> Table<S, A> {
     pub fn with_seed(seed: f64) -> Table<S, A> {
-        let map = HashMap::default();
         Table {
+            entries: RwLock::new(Entries {
+                map: HashMap::default(),
+                len: 0,
+            }),
             seed: seed,
-            map: map,
+            initializer: None,
+            insert: false,
+        }
+    }
+
+    pub fn with_initializer<F>(initializer: F) -> Table<S, A>
+    where
+        F: Fn(&S, &A) -> f64 + Send + Sync + 'static,
+    {
+        Table::initialized(initializer, false)
+    }
+
+    pub fn inserting<F>(initializer: F) -> Table<S, A>
+    where
+        F: Fn(&S, &A) -> f64 + Send + Sync + 'static,
+    {
+        Table::initialized(initializer, true)
+    }
+
+    fn initialized<F>(initializer: F, insert: bool) -> Table<S, A>
+    where
+        F: Fn(&S, &A) -> f64 + Send + Sync + 'static,
+    {
+        let mut table = Table::with_seed(0.);
+        table.initializer = Some(Arc::new(initializer));
+        table.insert = insert;
+        table
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entries(&self) -> RwLockReadGuard<'_, Entries<S, A>> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn entries_mut(&mut self) -> &mut Entries<S, A> {
+        self.entries.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S: Codec + Eq + Hash, A: Codec + Eq + Hash> Table<S, A> {
+    pub fn read_initialized<R, F>(reader: &mut R, initializer: F) -> io::Result<Table<S, A>>
+    where
+        R: Read,
+        F: Fn(&S, &A) -> f64 + Send + Sync + 'static,
+    {
+        let insert = match Option::<bool>::read_from(reader)? {
+            Some(insert) => insert,
+            None => return Err(invalid("table without initializer")),
+        };
+        let mut table = Table::initialized(initializer, insert);
+        table.read_entries(reader)?;
+        Ok(table)
+    }
+
+    fn read_entries<R: Read>(&mut self, reader: &mut R) -> io::Result<()> {
+        self.seed = f64::read_from(reader)?;
+        let entries = self.entries_mut();
+        for _ in 0..usize::read_from(reader)? {
+            let state = S::read_from(reader)?;
+            let action = A::read_from(reader)?;
+            let value = f64::read_from(reader)?;
+            entries.set(state, action, value);
         }
+        Ok(())
     }
 }
 
-impl<S: Eq + Hash, A: Eq + Hash> Memory<S, A> for Table<S, A> {
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Memory<S, A> for Table<S, A> {
     fn get(&self, state: &S, action: &A) -> f64 {
-        *self.map
-            .get(state)
-            .and_then(|map| map.get(action))
-            .unwrap_or(&self.seed)
+        if let Some(value) = self.entries().get(state, action) {
+            return value;
+        }
+
+        let initializer = match self.initializer {
+            Some(ref initializer) => initializer,
+            None => return self.seed,
+        };
+        if !self.insert {
+            return initializer(state, action);
+        }
+
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(state, action) {
+            Some(value) => value,
+            None => {
+                let value = initializer(state, action);
+                entries.set(state.clone(), action.clone(), value);
+                value
+            }
+        }
     }
 
     fn set(&mut self, state: S, action: A, value: f64) {
-        if self.map.contains_key(&state) {
-            let map = self.map.get_mut(&state).unwrap();
-            map.insert(action, value);
-        } else {
-            let mut map = HashMap::default();
-            map.insert(action, value);
-            self.map.insert(state, map);
+        self.entries_mut().set(state, action, value)
+    }
+}
+
+impl<S: Clone + Eq + Hash, A: Clone + Eq + Hash> Blend for Table<S, A> {
+    fn blend(&mut self, other: &Table<S, A>, tau: f64) {
+        for (state, map) in &other.entries().map {
+            for (action, value) in map {
+                let current = self.get(state, action);
+                self.set(
+                    state.clone(),
+                    action.clone(),
+                    (1. - tau) * current + tau * value,
+                );
+            }
         }
     }
 }
 
+impl<S: Codec + Eq + Hash, A: Codec + Eq + Hash> Codec for Table<S, A> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.initializer.as_ref().map(|_| self.insert).write_to(writer)?;
+        self.seed.write_to(writer)?;
+        let entries = self.entries();
+        entries.len.write_to(writer)?;
+        for (state, map) in &entries.map {
+            for (action, value) in map {
+                state.write_to(writer)?;
+                action.write_to(writer)?;
+                value.write_to(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Table<S, A>> {
+        if Option::<bool>::read_from(reader)?.is_some() {
+            return Err(invalid("initialized table"));
+        }
+        let mut table = Table::with_seed(0.);
+        table.read_entries(reader)?;
+        Ok(table)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Table;
-    use Memory;
+    use game::counter::*;
+    use memories::{Blend, Target};
+    use std::sync::{Arc, RwLock};
+    use std::thread::spawn;
+    use {Codec, Memory, Shared};
+
+    fn toward_zero(state: &i8, action: &Operation) -> f64 {
+        match (*state > 0, *action) {
+            (true, Operation::Dec) | (false, Operation::Inc) => 1.,
+            _ => -1.,
+        }
+    }
 
     #[test]
     fn seed_get_set_get() {
@@ -56,9 +227,115 @@ mod tests {
         assert_eq!(table.get(&4, &2), 3.1);
     }
 
+    #[test]
+    fn len_counts_state_actions() {
+        let mut table = Table::default();
+        assert!(table.is_empty());
+
+        table.set(4, 2, 1.);
+        table.set(4, 3, 1.);
+        table.set(2, 4, 1.);
+        table.set(4, 2, 2.);
+
+        assert_eq!(table.len(), 3);
+    }
+
     #[test]
     fn default_seeds_zero() {
         let table = Table::default();
         assert_eq!(table.get(&4, &2), 0.0);
     }
+
+    #[test]
+    fn blend_moves_toward_other() {
+        let mut table = Table::with_seed(1.);
+        let mut other = Table::default();
+
+        table.set(4, 2, 2.);
+        other.set(4, 2, 4.);
+        other.set(2, 4, 3.);
+
+        table.blend(&other, 0.25);
+
+        assert_eq!(table.get(&4, &2), 2.5);
+        assert_eq!(table.get(&2, &4), 1.5);
+        assert_eq!(table.get(&1, &1), 1.);
+    }
+
+    #[test]
+    fn codec_roundtrip() {
+        let mut table = Table::with_seed(0.5);
+        table.set(4u8, 2u8, 1.);
+        table.set(2, 4, -1.);
+
+        let mut bytes = vec![];
+        table.write_to(&mut bytes).unwrap();
+        let table: Table<u8, u8> = Table::read_from(&mut &bytes[..]).unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(&4, &2), 1.);
+        assert_eq!(table.get(&2, &4), -1.);
+        assert_eq!(table.get(&1, &1), 0.5);
+    }
+
+    #[test]
+    fn initializes_missing_entries() {
+        let mut table = Table::with_initializer(toward_zero);
+
+        assert_eq!(table.get(&3, &Operation::Dec), 1.);
+        assert_eq!(table.get(&-3, &Operation::Dec), -1.);
+        assert!(table.is_empty());
+
+        table.set(3, Operation::Dec, 0.5);
+        assert_eq!(table.get(&3, &Operation::Dec), 0.5);
+        assert_eq!(table.get(&3, &Operation::Inc), -1.);
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn initialized_tables_blend_and_share() {
+        let mut target = Target::hard(Table::with_initializer(toward_zero), 2);
+        target.set(3, Operation::Dec, 0.5);
+        assert_eq!(target.bootstrap(&3, &Operation::Inc), -1.);
+
+        let table = Arc::new(RwLock::new(Table::with_initializer(toward_zero)));
+        let writer = table.clone();
+        spawn(move || Shared::set(&*writer, 3, Operation::Inc, 2.))
+            .join()
+            .unwrap();
+
+        assert_eq!(Shared::get(&*table, &3, &Operation::Inc), 2.);
+        assert_eq!(Shared::get(&*table, &3, &Operation::Dec), 1.);
+    }
+
+    #[test]
+    fn inserts_on_first_access() {
+        let table = Table::inserting(toward_zero);
+
+        assert_eq!(table.get(&3, &Operation::Inc), -1.);
+        assert_eq!(table.get(&3, &Operation::Inc), -1.);
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn initializer_is_required_when_decoding() {
+        let table = Table::inserting(toward_zero);
+        table.get(&3, &Operation::Inc);
+        let mut table = table.clone();
+        table.set(3, Operation::Dec, 0.5);
+
+        let mut bytes = vec![];
+        table.write_to(&mut bytes).unwrap();
+        assert!(Table::<i8, Operation>::read_from(&mut &bytes[..]).is_err());
+
+        let table = Table::read_initialized(&mut &bytes[..], toward_zero).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get(&3, &Operation::Dec), 0.5);
+        assert_eq!(table.get(&-3, &Operation::Inc), 1.);
+        assert_eq!(table.len(), 3);
+
+        let mut bytes = vec![];
+        Table::<i8, Operation>::default().write_to(&mut bytes).unwrap();
+        assert!(Table::read_initialized(&mut &bytes[..], toward_zero).is_err());
+    }
 }
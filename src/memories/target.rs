@@ -0,0 +1,133 @@
+use Memory;
+
+pub trait Blend {
+    fn blend(&mut self, &Self, f64);
+}
+
+#[derive(Clone)]
+pub struct Target<M> {
+    online: M,
+    frozen: M,
+    every: usize,
+    tau: f64,
+    updates: usize,
+}
+
+impl<M: Clone> Target<M> {
+    pub fn hard(memory: M, every: usize) -> Target<M> {
+        Target::new(memory, every, 1.)
+    }
+
+    pub fn soft(memory: M, tau: f64) -> Target<M> {
+        Target::new(memory, 1, tau)
+    }
+
+    fn new(memory: M, every: usize, tau: f64) -> Target<M> {
+        assert!(every > 0, "target sync interval must be positive");
+        Target {
+            frozen: memory.clone(),
+            online: memory,
+            every: every,
+            tau: tau,
+            updates: 0,
+        }
+    }
+}
+
+impl<M> Target<M> {
+    pub fn online(&self) -> &M {
+        &self.online
+    }
+
+    pub fn frozen(&self) -> &M {
+        &self.frozen
+    }
+}
+
+impl<M: Blend> Target<M> {
+    fn sync(&mut self) {
+        self.updates += 1;
+
+        if self.updates == self.every {
+            self.frozen.blend(&self.online, self.tau);
+            self.updates = 0;
+        }
+    }
+}
+
+impl<S, A, M: Memory<S, A> + Blend> Memory<S, A> for Target<M> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.online.get(state, action)
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        self.online.set(state, action, value);
+        self.sync();
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.frozen.raw(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.online.raw(state, action)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: F) -> f64 {
+        let value = self.online.update(state, action, f);
+        self.sync();
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::{Bonus, Table};
+
+    #[test]
+    fn hard_syncs_every_n_updates() {
+        let mut target: Target<Table<i8, Operation>> = Target::hard(Table::default(), 2);
+
+        target.set(0, Operation::Inc, 1.);
+        assert_eq!(target.get(&0, &Operation::Inc), 1.);
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 0.);
+
+        target.set(0, Operation::Inc, 2.);
+        assert_eq!(target.get(&0, &Operation::Inc), 2.);
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 2.);
+
+        target.set(0, Operation::Inc, 3.);
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "target sync interval must be positive")]
+    fn rejects_zero_interval() {
+        Target::hard(Table::<i8, Operation>::default(), 0);
+    }
+
+    #[test]
+    fn bootstraps_without_exploration_bonus() {
+        let table: Table<i8, Operation> = Table::default();
+        let mut target = Target::hard(Bonus::new(table, 2.), 1);
+
+        target.set(0, Operation::Inc, 1.);
+
+        assert_eq!(target.get(&0, &Operation::Inc), 1. + 2. / 2f64.sqrt());
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 1.);
+    }
+
+    #[test]
+    fn soft_averages_every_update() {
+        let mut target: Target<Table<i8, Operation>> = Target::soft(Table::default(), 0.5);
+
+        target.set(0, Operation::Inc, 4.);
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 2.);
+
+        target.set(0, Operation::Inc, 4.);
+        assert_eq!(target.bootstrap(&0, &Operation::Inc), 3.);
+        assert_eq!(target.get(&0, &Operation::Inc), 4.);
+    }
+}
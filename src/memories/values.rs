@@ -0,0 +1,56 @@
+use ValueMemory;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Default)]
+pub struct ValueTable<S: Eq + Hash> {
+    map: HashMap<S, f64>,
+    seed: f64,
+}
+
+impl<S: Eq + Hash> ValueTable<S> {
+    pub fn with_seed(seed: f64) -> ValueTable<S> {
+        ValueTable {
+            map: HashMap::default(),
+            seed: seed,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<S: Eq + Hash> ValueMemory<S> for ValueTable<S> {
+    fn get(&self, state: &S) -> f64 {
+        *self.map.get(state).unwrap_or(&self.seed)
+    }
+
+    fn set(&mut self, state: S, value: f64) {
+        self.map.insert(state, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValueTable;
+    use ValueMemory;
+
+    #[test]
+    fn seed_get_set_get() {
+        let mut table = ValueTable::with_seed(1.5);
+        assert!(table.is_empty());
+        assert_eq!(table.get(&4), 1.5);
+
+        table.set(4, 2.);
+        table.set(4, 3.);
+
+        assert_eq!(table.get(&4), 3.);
+        assert_eq!(table.get(&2), 1.5);
+        assert_eq!(table.len(), 1);
+    }
+}
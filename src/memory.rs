@@ -1,4 +1,40 @@
 pub trait Memory<S, A> {
     fn get(&self, &S, &A) -> f64;
     fn set(&mut self, S, A, f64);
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.get(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.get(state, action)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: F) -> f64 {
+        let value = self.raw(&state, &action);
+        self.set(state, action, f(value));
+        value
+    }
+}
+
+impl<S, A, M: Memory<S, A>> Memory<S, A> for &mut M {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        (**self).get(state, action)
+    }
+
+    fn set(&mut self, state: S, action: A, value: f64) {
+        (**self).set(state, action, value)
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        (**self).bootstrap(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        (**self).raw(state, action)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&mut self, state: S, action: A, f: F) -> f64 {
+        (**self).update(state, action, f)
+    }
 }
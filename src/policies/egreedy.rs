@@ -1,4 +1,6 @@
 use Policy;
+use hooks::{Progress, Resume};
+use policies::Greedy;
 use rand::{thread_rng, Rng};
 
 pub struct Egreedy {
@@ -8,23 +10,48 @@ pub struct Egreedy {
 
 impl Egreedy {
     pub fn new(epsilon: f64) -> Egreedy {
+        Egreedy::with_rng(epsilon, thread_rng())
+    }
+
+    pub fn with_rng<R: Rng + 'static>(epsilon: f64, rng: R) -> Egreedy {
         Egreedy {
             epsilon: epsilon,
-            rng: Box::new(thread_rng()),
+            rng: Box::new(rng),
         }
     }
 }
 
-impl Policy for Egreedy {
-    fn choose<A>(&mut self, mut action_values: Vec<(A, f64)>) -> Option<A> {
+impl<S, A> Policy<S, A> for Egreedy {
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
         if self.rng.gen::<f64>() < self.epsilon {
             match action_values.len() {
                 0 => None,
-                n => Some(action_values.swap_remove(self.rng.gen_range(0, n)).0),
+                n => Some(self.rng.gen_range(0, n)),
             }
         } else {
-            action_values.sort_unstable_by(|&(_, v1), &(_, v2)| v1.partial_cmp(&v2).unwrap());
-            action_values.pop().map(|(a, _)| a)
+            Greedy.choose(state, action_values)
+        }
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let n = action_values.len() as f64;
+        Greedy.probabilities(state, action_values).map(|greedy| {
+            greedy
+                .into_iter()
+                .map(|p| self.epsilon / n + (1. - self.epsilon) * p)
+                .collect()
+        })
+    }
+
+    fn epsilon(&self, _: &S) -> f64 {
+        self.epsilon
+    }
+}
+
+impl Resume for Egreedy {
+    fn resume(&mut self, progress: &Progress) {
+        if progress.steps > 0 {
+            self.epsilon = progress.epsilon;
         }
     }
 }
@@ -38,7 +65,7 @@ mod tests {
     #[test]
     fn none_for_empty_action_values() {
         let mut egreedy = Egreedy::new(0.5);
-        assert_eq!(egreedy.choose(vec![]) as Option<Operation>, None);
+        assert_eq!(egreedy.choose(&0, &[] as &[(Operation, f64)]), None);
     }
 
     #[test]
@@ -50,10 +77,10 @@ mod tests {
 
         let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
 
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Dec));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Dec));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(0));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(0));
     }
 
     #[test]
@@ -65,10 +92,10 @@ mod tests {
 
         let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
 
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
     }
 
     #[test]
@@ -80,9 +107,18 @@ mod tests {
 
         let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
 
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Dec));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(egreedy.choose(action_values.clone()), Some(Operation::Inc));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(0));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+        assert_eq!(egreedy.choose(&0, &action_values), Some(1));
+    }
+
+    #[test]
+    fn mixes_greedy_and_uniform_probabilities() {
+        let egreedy = Egreedy::new(0.5);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(egreedy.probabilities(&0, &action_values), Some(vec![0.25, 0.75]));
     }
 }
@@ -1,13 +1,26 @@
 use Policy;
+use policy::argmax;
+use hooks::{Progress, Resume};
 
 #[derive(Default)]
 pub struct Greedy;
 
-impl Policy for Greedy {
-    fn choose<A>(&mut self, mut action_values: Vec<(A, f64)>) -> Option<A> {
-        action_values.sort_unstable_by(|&(_, v1), &(_, v2)| v1.partial_cmp(&v2).unwrap());
-        action_values.pop().map(|av| av.0)
+impl<S, A> Policy<S, A> for Greedy {
+    fn choose(&mut self, _: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        argmax(action_values.iter().map(|&(_, value)| value))
     }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let best = Greedy.choose(state, action_values);
+        Some(
+            (0..action_values.len())
+                .map(|i| if Some(i) == best { 1. } else { 0. })
+                .collect(),
+        )
+    }
+}
+impl Resume for Greedy {
+    fn resume(&mut self, _: &Progress) {}
 }
 
 #[cfg(test)]
@@ -18,7 +31,7 @@ mod tests {
     #[test]
     fn none_for_empty_action_values() {
         let mut greedy = Greedy::default();
-        assert_eq!(greedy.choose(vec![]) as Option<Operation>, None);
+        assert_eq!(greedy.choose(&0, &[] as &[(Operation, f64)]), None);
     }
 
     #[test]
@@ -28,7 +41,18 @@ mod tests {
         let action_values_1 = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
         let action_values_2 = vec![(Operation::Dec, 0.2), (Operation::Inc, 0.1)];
 
-        assert_eq!(greedy.choose(action_values_1), Some(Operation::Inc));
-        assert_eq!(greedy.choose(action_values_2), Some(Operation::Dec));
+        assert_eq!(greedy.choose(&0, &action_values_1), Some(1));
+        assert_eq!(greedy.choose(&0, &action_values_2), Some(0));
+    }
+
+    #[test]
+    fn puts_probability_on_chosen_tie() {
+        let mut greedy = Greedy::default();
+
+        let action_values = vec![(0, 0.2), (1, 0.1), (2, 0.2)];
+
+        assert_eq!(greedy.choose(&0, &action_values), Some(2));
+        assert_eq!(greedy.probabilities(&0, &action_values), Some(vec![0., 0., 1.]));
+        assert_eq!(greedy.probabilities(&0, &[] as &[(Operation, f64)]), Some(vec![]));
     }
 }
@@ -0,0 +1,72 @@
+use Policy;
+
+pub struct Override<F, P> {
+    heuristic: F,
+    policy: P,
+}
+
+impl<F, P> Override<F, P> {
+    pub fn new(heuristic: F, policy: P) -> Override<F, P> {
+        Override {
+            heuristic: heuristic,
+            policy: policy,
+        }
+    }
+}
+
+impl<S, A, F, P> Policy<S, A> for Override<F, P>
+where
+    F: Fn(&S, &[(A, f64)]) -> Option<usize>,
+    P: Policy<S, A>,
+{
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        match (self.heuristic)(state, action_values) {
+            Some(index) => Some(index),
+            None => self.policy.choose(state, action_values),
+        }
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        match (self.heuristic)(state, action_values) {
+            Some(index) => Some(
+                (0..action_values.len())
+                    .map(|i| if i == index { 1. } else { 0. })
+                    .collect(),
+            ),
+            None => self.policy.probabilities(state, action_values),
+        }
+    }
+
+    fn epsilon(&self, state: &S) -> f64 {
+        self.policy.epsilon(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use policies::Greedy;
+
+    fn down_from_two(state: &i8, action_values: &[(Operation, f64)]) -> Option<usize> {
+        if *state >= 2 {
+            action_values
+                .iter()
+                .position(|&(action, _)| action == Operation::Dec)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn heuristic_overrides_policy() {
+        let mut policy = Override::new(down_from_two, Greedy);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(policy.choose(&0, &action_values), Some(1));
+        assert_eq!(policy.choose(&2, &action_values), Some(0));
+        assert_eq!(policy.probabilities(&2, &action_values), Some(vec![1., 0.]));
+        assert_eq!(policy.probabilities(&1, &action_values), Some(vec![0., 1.]));
+    }
+}
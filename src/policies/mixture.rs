@@ -0,0 +1,152 @@
+use Policy;
+use rand::{thread_rng, Rng};
+
+pub struct Mixture<S, A> {
+    policies: Vec<(Box<Policy<S, A>>, f64)>,
+    total: f64,
+    rng: Box<Rng>,
+}
+
+impl<S, A> Mixture<S, A> {
+    pub fn new() -> Mixture<S, A> {
+        Mixture::with_rng(thread_rng())
+    }
+
+    pub fn with_rng<R: Rng + 'static>(rng: R) -> Mixture<S, A> {
+        Mixture {
+            policies: vec![],
+            total: 0.,
+            rng: Box::new(rng),
+        }
+    }
+
+    pub fn with<P: Policy<S, A> + 'static>(mut self, policy: P, weight: f64) -> Mixture<S, A> {
+        assert!(weight > 0., "weight must be positive");
+        self.policies.push((Box::new(policy), weight));
+        self.total += weight;
+        self
+    }
+}
+
+impl<S, A> Default for Mixture<S, A> {
+    fn default() -> Mixture<S, A> {
+        Mixture::new()
+    }
+}
+
+impl<S, A> Policy<S, A> for Mixture<S, A> {
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        let mut target = self.rng.gen::<f64>() * self.total;
+        let last = self.policies.len().checked_sub(1)?;
+
+        for (index, &mut (ref mut policy, weight)) in self.policies.iter_mut().enumerate() {
+            if target < weight || index == last {
+                return policy.choose(state, action_values);
+            }
+            target -= weight;
+        }
+        None
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let mut mixed = vec![0.; action_values.len()];
+        for &(ref policy, weight) in &self.policies {
+            let probabilities = policy.probabilities(state, action_values)?;
+            for (m, p) in mixed.iter_mut().zip(probabilities) {
+                *m += weight / self.total * p;
+            }
+        }
+        Some(mixed)
+    }
+
+    fn epsilon(&self, state: &S) -> f64 {
+        self.policies
+            .iter()
+            .map(|&(ref policy, weight)| weight / self.total * policy.epsilon(state))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use policies::{Egreedy, Greedy, Random, Scripted};
+    use rand::{SeedableRng, StdRng};
+
+    #[test]
+    fn mixes_probabilities() {
+        let mixture = Mixture::new()
+            .with(Greedy, 3.)
+            .with(Random::default(), 1.);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(
+            mixture.probabilities(&0, &action_values),
+            Some(vec![0.125, 0.875])
+        );
+        assert_eq!(mixture.epsilon(&0), 0.25);
+    }
+
+    #[test]
+    fn mixes_many_policies() {
+        let mixture = Mixture::new()
+            .with(Greedy, 1.)
+            .with(Random::default(), 2.)
+            .with(Egreedy::new(0.5), 1.);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(
+            mixture.probabilities(&0, &action_values),
+            Some(vec![0.3125, 0.6875])
+        );
+        assert_eq!(mixture.epsilon(&0), 0.625);
+    }
+
+    #[test]
+    fn picks_among_policies() {
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        let mut greedy = Mixture::with_rng(StdRng::from_seed(&[1])).with(Greedy, 1.);
+        for _ in 0..10 {
+            assert_eq!(greedy.choose(&0, &action_values), Some(1));
+        }
+
+        let mut random = Mixture::with_rng(StdRng::from_seed(&[1])).with(Random::default(), 1.);
+        let chosen: Vec<_> = (0..20)
+            .map(|_| random.choose(&0, &action_values))
+            .collect();
+        assert!(chosen.contains(&Some(0)));
+
+        let mut empty: Mixture<i8, Operation> = Mixture::default();
+        assert_eq!(empty.choose(&0, &action_values), None);
+    }
+
+    #[test]
+    fn mixes_script_with_learner() {
+        let script = Scripted::new(|_: &i8| Some(Operation::Dec));
+        let mut mixture = Mixture::with_rng(StdRng::from_seed(&[1]))
+            .with(script, 1.)
+            .with(Greedy, 1.);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        let chosen: Vec<_> = (0..20)
+            .map(|_| mixture.choose(&0, &action_values))
+            .collect();
+        assert!(chosen.contains(&Some(0)));
+        assert!(chosen.contains(&Some(1)));
+        assert_eq!(
+            mixture.probabilities(&0, &action_values),
+            Some(vec![0.5, 0.5])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "weight must be positive")]
+    fn rejects_zero_weight() {
+        Mixture::<i8, Operation>::new().with(Greedy, 0.);
+    }
+}
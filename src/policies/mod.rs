@@ -1,7 +1,17 @@
 mod random;
 mod greedy;
 mod egreedy;
+mod thompson;
+mod mixture;
+mod schedule;
+mod heuristic;
+mod scripted;
 
 pub use self::random::Random;
 pub use self::greedy::Greedy;
 pub use self::egreedy::Egreedy;
+pub use self::thompson::Thompson;
+pub use self::mixture::Mixture;
+pub use self::schedule::Schedule;
+pub use self::heuristic::Override;
+pub use self::scripted::Scripted;
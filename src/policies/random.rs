@@ -1,25 +1,43 @@
 use Policy;
+use hooks::{Progress, Resume};
 use rand::{thread_rng, Rng};
 
 pub struct Random {
     rng: Box<Rng>,
 }
 
+impl Random {
+    pub fn with_rng<R: Rng + 'static>(rng: R) -> Random {
+        Random { rng: Box::new(rng) }
+    }
+}
+
 impl Default for Random {
     fn default() -> Random {
-        Random {
-            rng: Box::new(thread_rng()),
-        }
+        Random::with_rng(thread_rng())
     }
 }
 
-impl Policy for Random {
-    fn choose<A>(&mut self, mut action_values: Vec<(A, f64)>) -> Option<A> {
+impl<S, A> Policy<S, A> for Random {
+    fn choose(&mut self, _: &S, action_values: &[(A, f64)]) -> Option<usize> {
         match action_values.len() {
             0 => None,
-            n => Some(action_values.swap_remove(self.rng.gen_range(0, n)).0),
+            n => Some(self.rng.gen_range(0, n)),
         }
     }
+
+    fn probabilities(&self, _: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let n = action_values.len() as f64;
+        Some(action_values.iter().map(|_| 1. / n).collect())
+    }
+
+    fn epsilon(&self, _: &S) -> f64 {
+        1.
+    }
+}
+
+impl Resume for Random {
+    fn resume(&mut self, _: &Progress) {}
 }
 
 #[cfg(test)]
@@ -31,7 +49,7 @@ mod tests {
     #[test]
     fn none_for_empty_action_values() {
         let mut random = Random::default();
-        assert_eq!(random.choose(vec![]) as Option<Operation>, None);
+        assert_eq!(random.choose(&0, &[] as &[(Operation, f64)]), None);
     }
 
     #[test]
@@ -45,7 +63,16 @@ mod tests {
 
         let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
 
-        assert_eq!(random1.choose(action_values.clone()), Some(Operation::Inc));
-        assert_eq!(random2.choose(action_values.clone()), Some(Operation::Dec));
+        assert_eq!(random1.choose(&0, &action_values), Some(1));
+        assert_eq!(random2.choose(&0, &action_values), Some(0));
+    }
+
+    #[test]
+    fn uniform_probabilities() {
+        let random = Random::default();
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(random.probabilities(&0, &action_values), Some(vec![0.5, 0.5]));
     }
 }
@@ -0,0 +1,115 @@
+use Policy;
+use hooks::{Progress, Resume};
+
+pub struct Schedule<P, Q> {
+    first: P,
+    second: Q,
+    steps: usize,
+    count: usize,
+}
+
+impl<P, Q> Schedule<P, Q> {
+    pub fn new(first: P, second: Q, steps: usize) -> Schedule<P, Q> {
+        Schedule {
+            first: first,
+            second: second,
+            steps: steps,
+            count: 0,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn switched(&self) -> bool {
+        self.count >= self.steps
+    }
+}
+
+impl<S, A, P: Policy<S, A>, Q: Policy<S, A>> Policy<S, A> for Schedule<P, Q> {
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        let switched = self.switched();
+        self.count += 1;
+
+        if switched {
+            self.second.choose(state, action_values)
+        } else {
+            self.first.choose(state, action_values)
+        }
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        if self.switched() {
+            self.second.probabilities(state, action_values)
+        } else {
+            self.first.probabilities(state, action_values)
+        }
+    }
+
+    fn epsilon(&self, state: &S) -> f64 {
+        if self.switched() {
+            self.second.epsilon(state)
+        } else {
+            self.first.epsilon(state)
+        }
+    }
+}
+
+impl<P: Resume, Q: Resume> Resume for Schedule<P, Q> {
+    fn resume(&mut self, progress: &Progress) {
+        if progress.steps == 0 {
+            return;
+        }
+        self.count = progress.steps;
+        if self.switched() {
+            self.second.resume(progress);
+        } else {
+            self.first.resume(progress);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Belief;
+    use policies::{Egreedy, Override, Random, Thompson};
+    use std::sync::{Arc, RwLock};
+    use Memory;
+
+    #[test]
+    fn switches_after_steps() {
+        let mut schedule = Schedule::new(Random::default(), Egreedy::new(0.5), 2);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(Policy::<i8, Operation>::epsilon(&schedule, &0), 1.);
+        schedule.choose(&0, &action_values);
+        assert_eq!(schedule.probabilities(&0, &action_values), Some(vec![0.5, 0.5]));
+        schedule.choose(&0, &action_values);
+
+        assert_eq!(schedule.count(), 2);
+        assert_eq!(Policy::<i8, Operation>::epsilon(&schedule, &0), 0.5);
+        assert_eq!(schedule.probabilities(&0, &action_values), Some(vec![0.25, 0.75]));
+    }
+
+    #[test]
+    fn hands_over_to_state_aware_policies() {
+        let mut belief = Belief::default();
+        for _ in 0..100 {
+            belief.set(0, Operation::Dec, 1.);
+            belief.set(0, Operation::Inc, -1.);
+        }
+        let thompson = Thompson::new(Arc::new(RwLock::new(belief)));
+        let stop = |state: &i8, _: &[(Operation, f64)]| if *state > 0 { Some(0) } else { None };
+        let mut schedule = Schedule::new(Egreedy::new(0.), Override::new(stop, thompson), 1);
+
+        let action_values = vec![(Operation::Dec, 0.1), (Operation::Inc, 0.2)];
+
+        assert_eq!(schedule.choose(&0, &action_values), Some(1));
+        assert_eq!(schedule.choose(&0, &action_values), Some(0));
+        assert_eq!(schedule.choose(&1, &action_values), Some(0));
+    }
+}
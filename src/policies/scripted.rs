@@ -0,0 +1,51 @@
+use Policy;
+
+pub struct Scripted<F> {
+    script: F,
+}
+
+impl<F> Scripted<F> {
+    pub fn new(script: F) -> Scripted<F> {
+        Scripted { script: script }
+    }
+}
+
+impl<S, A: PartialEq, F: Fn(&S) -> Option<A>> Policy<S, A> for Scripted<F> {
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        let action = (self.script)(state)?;
+        action_values.iter().position(|(a, _)| *a == action)
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let action = (self.script)(state);
+        Some(
+            action_values
+                .iter()
+                .map(|(a, _)| if Some(a) == action.as_ref() { 1. } else { 0. })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn follows_script() {
+        let mut scripted = Scripted::new(|state: &i8| match *state {
+            0 => Some(Operation::Inc),
+            1 => Some(Operation::Dec),
+            _ => None,
+        });
+
+        let action_values = vec![(Operation::Dec, 0.2), (Operation::Inc, 0.1)];
+
+        assert_eq!(scripted.choose(&0, &action_values), Some(1));
+        assert_eq!(scripted.choose(&1, &action_values), Some(0));
+        assert_eq!(scripted.choose(&2, &action_values), None);
+        assert_eq!(scripted.choose(&0, &action_values[..1]), None);
+        assert_eq!(scripted.probabilities(&0, &action_values), Some(vec![0., 1.]));
+    }
+}
@@ -0,0 +1,124 @@
+use Policy;
+use memories::Belief;
+use rand::{thread_rng, Rng};
+use std::cell::RefCell;
+use std::hash::Hash;
+use std::sync::{Arc, PoisonError, RwLock};
+
+const DRAWS: usize = 1000;
+
+pub struct Thompson<S: Eq + Hash, A> {
+    belief: Arc<RwLock<Belief<S, A>>>,
+    rng: RefCell<Box<Rng>>,
+}
+
+impl<S: Eq + Hash, A: Eq + Hash> Thompson<S, A> {
+    pub fn new(belief: Arc<RwLock<Belief<S, A>>>) -> Thompson<S, A> {
+        Thompson::with_rng(belief, thread_rng())
+    }
+
+    pub fn with_rng<R: Rng + 'static>(
+        belief: Arc<RwLock<Belief<S, A>>>,
+        rng: R,
+    ) -> Thompson<S, A> {
+        Thompson {
+            belief: belief,
+            rng: RefCell::new(Box::new(rng)),
+        }
+    }
+
+    fn draw<R: Rng>(
+        &self,
+        mut rng: R,
+        state: &S,
+        action_values: &[(A, f64)],
+    ) -> Option<usize> {
+        let belief = self.belief.read().unwrap_or_else(PoisonError::into_inner);
+
+        let mut best: Option<(usize, f64)> = None;
+        for (index, (action, _)) in action_values.iter().enumerate() {
+            let value = belief.posterior(state, action).sample(&mut rng);
+            match best {
+                Some((_, max)) if value <= max => {}
+                _ => best = Some((index, value)),
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+}
+
+impl<S: Eq + Hash, A: Eq + Hash> Policy<S, A> for Thompson<S, A> {
+    fn choose(&mut self, state: &S, action_values: &[(A, f64)]) -> Option<usize> {
+        let mut rng = self.rng.borrow_mut();
+        self.draw(&mut *rng, state, action_values)
+    }
+
+    fn probabilities(&self, state: &S, action_values: &[(A, f64)]) -> Option<Vec<f64>> {
+        let mut rng = self.rng.borrow_mut();
+        let mut counts = vec![0.; action_values.len()];
+        for _ in 0..DRAWS {
+            if let Some(index) = self.draw(&mut *rng, state, action_values) {
+                counts[index] += 1.;
+            }
+        }
+        Some(counts.iter().map(|count| count / DRAWS as f64).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use rand::{SeedableRng, StdRng};
+    use Memory;
+
+    fn thompson() -> Thompson<i8, Operation> {
+        let mut belief = Belief::default();
+        for _ in 0..100 {
+            belief.set(0, Operation::Inc, 1.);
+            belief.set(0, Operation::Dec, -1.);
+        }
+        Thompson::with_rng(Arc::new(RwLock::new(belief)), StdRng::from_seed(&[1]))
+    }
+
+    #[test]
+    fn none_for_empty_action_values() {
+        assert_eq!(thompson().choose(&0, &[]), None);
+    }
+
+    #[test]
+    fn samples_confident_best_action() {
+        let mut thompson = thompson();
+        let action_values = vec![(Operation::Dec, 0.), (Operation::Inc, 0.)];
+
+        for _ in 0..10 {
+            assert_eq!(thompson.choose(&0, &action_values), Some(1));
+        }
+        let probabilities = thompson.probabilities(&0, &action_values).unwrap();
+        assert!(probabilities[1] > 0.99);
+        assert!((probabilities[0] + probabilities[1] - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn seeded_probabilities_are_reproducible() {
+        let action_values = vec![(Operation::Dec, 0.), (Operation::Inc, 0.)];
+
+        assert_eq!(
+            thompson().probabilities(&1, &action_values),
+            thompson().probabilities(&1, &action_values)
+        );
+    }
+
+    #[test]
+    fn explores_uncertain_actions() {
+        let mut thompson = thompson();
+        let action_values = vec![(Operation::Dec, 0.), (Operation::Inc, 0.)];
+
+        let chosen: Vec<_> = (0..50)
+            .map(|_| thompson.choose(&1, &action_values))
+            .collect();
+
+        assert!(chosen.contains(&Some(0)));
+        assert!(chosen.contains(&Some(1)));
+    }
+}
@@ -1,3 +1,22 @@
-pub trait Policy {
-    fn choose<A>(&mut self, Vec<(A, f64)>) -> Option<A>;
+pub trait Policy<S, A> {
+    fn choose(&mut self, &S, &[(A, f64)]) -> Option<usize>;
+
+    fn probabilities(&self, _: &S, _: &[(A, f64)]) -> Option<Vec<f64>> {
+        None
+    }
+
+    fn epsilon(&self, _: &S) -> f64 {
+        0.
+    }
+}
+
+pub fn argmax<I: IntoIterator<Item = f64>>(values: I) -> Option<usize> {
+    let mut best: Option<(usize, f64)> = None;
+    for (index, value) in values.into_iter().enumerate() {
+        match best {
+            Some((_, max)) if value < max => {}
+            _ => best = Some((index, value)),
+        }
+    }
+    best.map(|(index, _)| index)
 }
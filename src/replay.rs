@@ -0,0 +1,94 @@
+use Sample;
+use rand::{thread_rng, Rng};
+
+pub struct Replay<S, A> {
+    samples: Vec<Sample<S, A>>,
+    capacity: usize,
+    next: usize,
+    rng: Box<Rng>,
+}
+
+impl<S, A> Replay<S, A> {
+    pub fn new(capacity: usize) -> Replay<S, A> {
+        Replay::with_rng(capacity, thread_rng())
+    }
+
+    pub fn with_rng<R: Rng + 'static>(capacity: usize, rng: R) -> Replay<S, A> {
+        assert!(capacity > 0, "replay capacity must be positive");
+        Replay {
+            samples: Vec::with_capacity(capacity),
+            capacity: capacity,
+            next: 0,
+            rng: Box::new(rng),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn push(&mut self, sample: Sample<S, A>) {
+        if self.samples.len() < self.capacity {
+            self.samples.push(sample);
+        } else {
+            self.samples[self.next] = sample;
+        }
+        self.next = (self.next + 1) % self.capacity;
+    }
+
+    pub fn batch(&mut self, size: usize) -> Vec<&Sample<S, A>> {
+        let samples = &self.samples;
+        let rng = &mut self.rng;
+
+        match samples.len() {
+            0 => vec![],
+            n => (0..size).map(|_| &samples[rng.gen_range(0, n)]).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, StdRng};
+    use game::counter::*;
+    use super::*;
+
+    #[test]
+    fn empty_batch_for_empty_replay() {
+        let mut replay: Replay<i8, Operation> = Replay::new(2);
+        assert!(replay.batch(3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "replay capacity must be positive")]
+    fn rejects_zero_capacity() {
+        Replay::<i8, Operation>::new(0);
+    }
+
+    #[test]
+    fn overwrites_oldest_when_full() {
+        let mut replay = Replay::new(2);
+
+        replay.push((0, Operation::Inc, 1, 1.));
+        replay.push((1, Operation::Inc, 2, 1.));
+        replay.push((2, Operation::Dec, 1, -1.));
+
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay.samples[0], (2, Operation::Dec, 1, -1.));
+        assert_eq!(replay.samples[1], (1, Operation::Inc, 2, 1.));
+    }
+
+    #[test]
+    fn batch_samples_with_replacement() {
+        let mut replay = Replay::with_rng(2, StdRng::from_seed(&[1]));
+
+        replay.push((0, Operation::Inc, 1, 1.));
+        replay.push((1, Operation::Dec, 0, -1.));
+
+        assert_eq!(replay.batch(5).len(), 5);
+    }
+}
@@ -0,0 +1,89 @@
+use Memory;
+use std::cell::RefCell;
+use std::sync::{Arc, PoisonError, RwLock};
+
+pub trait Shared<S, A> {
+    fn get(&self, &S, &A) -> f64;
+    fn set(&self, S, A, f64);
+    fn update<F: FnOnce(f64) -> f64>(&self, S, A, F) -> Option<f64>;
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.get(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.get(state, action)
+    }
+}
+
+impl<S, A, M: Memory<S, A>> Shared<S, A> for RwLock<M> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(state, action)
+    }
+
+    fn set(&self, state: S, action: A, value: f64) {
+        self.write().unwrap().set(state, action, value)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&self, state: S, action: A, f: F) -> Option<f64> {
+        Some(self.write().ok()?.update(state, action, f))
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .bootstrap(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .raw(state, action)
+    }
+}
+
+impl<S, A, C: Shared<S, A>> Shared<S, A> for Arc<C> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        (**self).get(state, action)
+    }
+
+    fn set(&self, state: S, action: A, value: f64) {
+        (**self).set(state, action, value)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&self, state: S, action: A, f: F) -> Option<f64> {
+        (**self).update(state, action, f)
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        (**self).bootstrap(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        (**self).raw(state, action)
+    }
+}
+
+impl<S, A, M: Memory<S, A>> Shared<S, A> for RefCell<M> {
+    fn get(&self, state: &S, action: &A) -> f64 {
+        self.borrow().get(state, action)
+    }
+
+    fn set(&self, state: S, action: A, value: f64) {
+        self.borrow_mut().set(state, action, value)
+    }
+
+    fn update<F: FnOnce(f64) -> f64>(&self, state: S, action: A, f: F) -> Option<f64> {
+        Some(self.borrow_mut().update(state, action, f))
+    }
+
+    fn bootstrap(&self, state: &S, action: &A) -> f64 {
+        self.borrow().bootstrap(state, action)
+    }
+
+    fn raw(&self, state: &S, action: &A) -> f64 {
+        self.borrow().raw(state, action)
+    }
+}
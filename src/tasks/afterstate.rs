@@ -0,0 +1,95 @@
+use {Brain, Game, Hook, Policy, ValueMemory};
+use super::step::{act, action_values, learn_value, Episodes};
+use super::Control;
+
+pub fn rollout<G, V>(game: &G, values: &V, brain: &Brain) -> Vec<(G::Action, f64)>
+where
+    G: Game + Clone,
+    V: ValueMemory<G::State>,
+{
+    action_values(game, |_, action| {
+        let mut afterstate = game.clone();
+        afterstate.act(action);
+        let next = if afterstate.finished() {
+            0.
+        } else {
+            values.get(&afterstate.state())
+        };
+        brain.target(next, afterstate.reward())
+    })
+}
+
+pub fn afterstates<G, P, V, H>(
+    game: &mut G,
+    policy: &mut P,
+    values: &mut V,
+    brain: &Brain,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game + Clone,
+    P: Policy<G::State, G::Action>,
+    V: ValueMemory<G::State>,
+    H: Hook<G::State, G::Action>,
+{
+    let mut episodes = Episodes::new();
+
+    while !control.stopped() {
+        let action_values = rollout(game, values, brain);
+        let sample = match act(game, policy, action_values, hook) {
+            Some(sample) => sample,
+            None => break,
+        };
+        let reward = sample.3;
+        let finished = game.finished();
+
+        learn_value(values, brain, sample, finished, hook, control);
+        episodes.record(reward, finished, hook, control);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::ValueTable;
+    use policies::Greedy;
+    use tasks::Limits;
+
+    #[test]
+    fn rolls_out_with_state_values() {
+        let mut values = ValueTable::default();
+        values.set(1, 3.);
+        values.set(-1, 10.);
+
+        assert_eq!(
+            rollout(&Counter::default(), &values, &Brain::new(0.5, 0.5)),
+            vec![(Operation::Dec, 4.), (Operation::Inc, 2.5)]
+        );
+        assert_eq!(
+            rollout(&Capped::default(), &values, &Brain::new(0.5, 0.5)),
+            vec![(Operation::Dec, 4.)]
+        );
+    }
+
+    #[test]
+    fn learns_one_value_per_state() {
+        let mut game = Counter::default();
+        let mut values = ValueTable::default();
+
+        afterstates(
+            &mut game,
+            &mut Greedy,
+            &mut values,
+            &Brain::new(0.5, 0.5),
+            &Control::new(Limits {
+                samples: Some(256 * 40),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        assert_eq!(values.len(), 256);
+        assert!((values.get(&0) - 2.).abs() < 1e-3);
+    }
+}
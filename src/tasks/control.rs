@@ -0,0 +1,203 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    pub samples: Option<usize>,
+    pub episodes: Option<usize>,
+    pub duration: Option<Duration>,
+    pub convergence: Option<(f64, usize)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub samples: usize,
+    pub episodes: usize,
+    pub elapsed: Duration,
+    pub delta: f64,
+}
+
+#[derive(Debug)]
+pub struct Control {
+    limits: Limits,
+    start: Instant,
+    samples: AtomicUsize,
+    episodes: AtomicUsize,
+    calm: AtomicUsize,
+    delta: AtomicU64,
+    stopped: AtomicBool,
+}
+
+impl Default for Control {
+    fn default() -> Control {
+        Control::new(Limits::default())
+    }
+}
+
+impl Control {
+    pub fn new(limits: Limits) -> Control {
+        Control {
+            limits: limits,
+            start: Instant::now(),
+            samples: AtomicUsize::new(0),
+            episodes: AtomicUsize::new(0),
+            calm: AtomicUsize::new(0),
+            delta: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    pub fn limited(&self) -> bool {
+        self.limits != Limits::default()
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn stopped(&self) -> bool {
+        if self.stopped.load(Ordering::SeqCst) {
+            return true;
+        }
+
+        let limits = &self.limits;
+        let reached = exceeds(limits.samples, self.samples.load(Ordering::SeqCst))
+            || exceeds(limits.episodes, self.episodes.load(Ordering::SeqCst))
+            || exceeds(limits.duration, self.start.elapsed())
+            || exceeds(
+                limits.convergence.map(|(_, n)| n),
+                self.calm.load(Ordering::SeqCst),
+            );
+
+        if reached {
+            self.stop();
+        }
+        reached
+    }
+
+    pub fn sample(&self, delta: f64) {
+        self.samples.fetch_add(1, Ordering::SeqCst);
+        self.delta.store(delta.to_bits(), Ordering::SeqCst);
+
+        if let Some((tolerance, _)) = self.limits.convergence {
+            if delta.abs() < tolerance {
+                self.calm.fetch_add(1, Ordering::SeqCst);
+            } else {
+                self.calm.store(0, Ordering::SeqCst);
+            }
+        }
+    }
+
+    pub fn episode(&self) {
+        self.episodes.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn summary(&self) -> Summary {
+        Summary {
+            samples: self.samples.load(Ordering::SeqCst),
+            episodes: self.episodes.load(Ordering::SeqCst),
+            elapsed: self.start.elapsed(),
+            delta: f64::from_bits(self.delta.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+fn exceeds<T: PartialOrd>(limit: Option<T>, value: T) -> bool {
+    match limit {
+        Some(limit) => value >= limit,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn unlimited_until_stopped() {
+        let control = Control::default();
+
+        control.sample(1.);
+        control.episode();
+        assert!(!control.stopped());
+
+        control.stop();
+        assert!(control.stopped());
+    }
+
+    #[test]
+    fn limited_with_any_limit() {
+        assert!(!Control::default().limited());
+        assert!(Control::new(Limits {
+            episodes: Some(1),
+            ..Limits::default()
+        }).limited());
+    }
+
+    #[test]
+    fn stops_after_samples() {
+        let control = Control::new(Limits {
+            samples: Some(2),
+            ..Limits::default()
+        });
+
+        control.sample(1.);
+        assert!(!control.stopped());
+        control.sample(1.);
+        assert!(control.stopped());
+    }
+
+    #[test]
+    fn stops_after_episodes() {
+        let control = Control::new(Limits {
+            episodes: Some(1),
+            ..Limits::default()
+        });
+
+        control.sample(1.);
+        assert!(!control.stopped());
+        control.episode();
+        assert!(control.stopped());
+    }
+
+    #[test]
+    fn stops_after_duration() {
+        let control = Control::new(Limits {
+            duration: Some(Duration::from_millis(1)),
+            ..Limits::default()
+        });
+
+        sleep(Duration::from_millis(2));
+        assert!(control.stopped());
+    }
+
+    #[test]
+    fn stops_after_consecutive_small_deltas() {
+        let control = Control::new(Limits {
+            convergence: Some((0.1, 2)),
+            ..Limits::default()
+        });
+
+        control.sample(0.01);
+        control.sample(0.5);
+        control.sample(0.01);
+        assert!(!control.stopped());
+        control.sample(-0.01);
+        assert!(control.stopped());
+    }
+
+    #[test]
+    fn summary_counts() {
+        let control = Control::default();
+
+        control.sample(0.5);
+        control.sample(0.25);
+        control.episode();
+
+        let summary = control.summary();
+        assert_eq!(summary.samples, 2);
+        assert_eq!(summary.episodes, 1);
+        assert_eq!(summary.delta, 0.25);
+    }
+}
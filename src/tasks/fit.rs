@@ -0,0 +1,159 @@
+use {Brain, Game, Hook, Memory, Policy, Sample, Update};
+use super::Control;
+
+pub fn fit<G, P, M, H>(
+    game: &G,
+    policy: &mut P,
+    memory: &mut M,
+    samples: &[Sample<G::State, G::Action>],
+    brain: &Brain,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    M: Memory<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    while !samples.is_empty() && !control.stopped() {
+        let targets: Vec<f64> = samples
+            .iter()
+            .map(|sample| {
+                let mut action_values = vec![];
+
+                for action in game.actions() {
+                    if !game.legal(&sample.2, &action) {
+                        continue;
+                    }
+                    let value = memory.raw(&sample.2, &action);
+                    action_values.push((action, value));
+                }
+
+                policy
+                    .choose(&sample.2, &action_values)
+                    .map_or(0., |index| memory.bootstrap(&sample.2, &action_values[index].0))
+            })
+            .collect();
+
+        for (sample, value1) in samples.iter().zip(targets) {
+            if control.stopped() {
+                return;
+            }
+
+            let value0 = memory.raw(&sample.0, &sample.1);
+            let learned = brain.learn(value0, value1, sample.3);
+
+            hook.update(&Update {
+                sample: sample,
+                error: brain.error(value0, value1, sample.3),
+                delta: learned - value0,
+            });
+
+            memory.set(sample.0.clone(), sample.1.clone(), learned);
+            control.sample(learned - value0);
+        }
+
+        if !control.limited() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use hooks::{Recorder, Samples, Throughput};
+    use memories::Table;
+    use policies::Greedy;
+    use std::io;
+    use std::sync::mpsc::channel;
+    use std::sync::RwLock;
+    use tasks::{play_until, Limits};
+
+    #[test]
+    fn fits_against_previous_iteration() {
+        let samples = vec![(0, Operation::Inc, 1, 1.), (1, Operation::Dec, 0, -1.)];
+        let mut memory: Table<i8, Operation> = Table::default();
+        let mut throughput = Throughput::default();
+
+        fit(
+            &Counter::default(),
+            &mut Greedy::default(),
+            &mut memory,
+            &samples,
+            &Brain::new(1., 0.5),
+            &Control::new(Limits {
+                samples: Some(4),
+                ..Limits::default()
+            }),
+            &mut throughput,
+        );
+
+        assert_eq!(memory.get(&0, &Operation::Inc), 1.);
+        assert_eq!(memory.get(&1, &Operation::Dec), -0.5);
+        assert_eq!(throughput.updates(), 4);
+    }
+
+    #[test]
+    fn single_pass_without_limits() {
+        let samples = vec![(0, Operation::Inc, 1, 1.), (1, Operation::Dec, 0, -1.)];
+        let mut memory: Table<i8, Operation> = Table::default();
+        let control = Control::default();
+
+        fit(
+            &Counter::default(),
+            &mut Greedy::default(),
+            &mut memory,
+            &samples,
+            &Brain::new(1., 0.5),
+            &control,
+            &mut (),
+        );
+
+        assert_eq!(control.summary().samples, 2);
+        assert_eq!(memory.get(&0, &Operation::Inc), 1.);
+    }
+
+    #[test]
+    fn fits_recorded_play() {
+        let (sender, receiver) = channel();
+        drop(receiver);
+
+        let mut recorder = Recorder::new(vec![]);
+        let table: Table<i8, Operation> = Table::default();
+        play_until(
+            &mut Counter::default(),
+            &mut Greedy::default(),
+            &RwLock::new(table),
+            &sender,
+            &Control::default(),
+            &mut recorder,
+        );
+        assert_eq!(recorder.samples(), 1);
+
+        let bytes = recorder.finish().unwrap();
+        let samples: Vec<Sample<i8, Operation>> = Samples::new(&bytes[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let mut memory = Table::default();
+
+        fit(
+            &Counter::default(),
+            &mut Greedy::default(),
+            &mut memory,
+            &samples,
+            &Brain::new(0.5, 0.),
+            &Control::new(Limits {
+                convergence: Some((1e-6, 1)),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        let (state, action, _, reward) = samples[0];
+        assert!((memory.get(&state, &action) - reward).abs() < 1e-5);
+    }
+}
@@ -1,7 +1,26 @@
 mod play;
 mod train;
 mod walk;
+mod replay;
+mod control;
+mod runner;
+mod run;
+mod fit;
+mod reproduce;
+mod pretrain;
+mod predict;
+mod afterstate;
+mod step;
 
-pub use self::play::play;
-pub use self::train::train;
-pub use self::walk::walk;
+pub use self::play::{play, play_until, Sink};
+pub use self::train::{train, train_until};
+pub use self::walk::{walk, walk_with};
+pub use self::replay::replay;
+pub use self::control::{Control, Limits, Summary};
+pub use self::runner::{spawn, Finished, Runner};
+pub use self::run::run;
+pub use self::fit::fit;
+pub use self::reproduce::reproduce;
+pub use self::pretrain::pretrain;
+pub use self::predict::predict;
+pub use self::afterstate::{afterstates, rollout};
@@ -1,32 +1,62 @@
-use {Game, Memory, Policy, Sample};
-use std::sync::mpsc::Sender;
-use std::sync::RwLock;
+use {Game, Hook, Policy, Sample, Shared};
+use std::sync::mpsc::{SendError, Sender, SyncSender};
+use super::step::{act, action_values, Episodes};
+use super::Control;
 
-pub fn play<G: Game, P: Policy, M: Memory<G::State, G::Action>>(
+pub trait Sink<T> {
+    fn send(&self, T) -> Result<(), SendError<T>>;
+}
+
+impl<T> Sink<T> for Sender<T> {
+    fn send(&self, value: T) -> Result<(), SendError<T>> {
+        Sender::send(self, value)
+    }
+}
+
+impl<T> Sink<T> for SyncSender<T> {
+    fn send(&self, value: T) -> Result<(), SendError<T>> {
+        SyncSender::send(self, value)
+    }
+}
+
+pub fn play<G, P, C>(
     game: &mut G,
     policy: &mut P,
-    memory: &RwLock<M>,
+    memory: &C,
     sender: &Sender<Sample<G::State, G::Action>>,
-) {
-    loop {
-        let state = game.state();
-        let mut action_values = vec![];
-        let memory = memory.read().unwrap();
-
-        for action in game.actions() {
-            let value = memory.get(&state, &action);
-            action_values.push((action, value));
-        }
+) where
+    G: Game,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+{
+    play_until(game, policy, memory, sender, &Control::default(), &mut ())
+}
 
-        let action = match policy.choose(action_values) {
-            Some(action) => action,
+pub fn play_until<G, P, C, Q, H>(
+    game: &mut G,
+    policy: &mut P,
+    memory: &C,
+    sender: &Q,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+    Q: Sink<Sample<G::State, G::Action>>,
+    H: Hook<G::State, G::Action>,
+{
+    let mut episodes = Episodes::new();
+
+    while !control.stopped() {
+        let action_values = action_values(game, |state, action| memory.get(state, action));
+        let sample = match act(game, policy, action_values, hook) {
+            Some(sample) => sample,
             None => break,
         };
+        let finished = game.finished();
 
-        game.act(&action);
-        let reward = game.reward();
-        let next_state = game.state();
-        let sample = (state, action, next_state, reward);
+        episodes.record(sample.3, finished, hook, control);
 
         if sender.send(sample).is_err() {
             break;
@@ -38,10 +68,11 @@ pub fn play<G: Game, P: Policy, M: Memory<G::State, G::Action>>(
 mod tests {
     use super::*;
     use game::counter::*;
-    use std::sync::mpsc::channel;
+    use std::sync::mpsc::{channel, sync_channel};
     use std::thread::spawn;
     use policies::Greedy;
     use memories::Table;
+    use std::sync::RwLock;
     use Memory;
 
     #[test]
@@ -66,4 +97,71 @@ mod tests {
         assert_eq!(receiver.recv().unwrap(), (0, Operation::Inc, 1, 1.));
         assert_eq!(receiver.recv().unwrap(), (1, Operation::Dec, 0, -1.));
     }
+
+    struct Unasked;
+
+    impl Policy<i8, Operation> for Unasked {
+        fn choose(&mut self, state: &i8, action_values: &[(Operation, f64)]) -> Option<usize> {
+            Greedy.choose(state, action_values)
+        }
+
+        fn probabilities(&self, _: &i8, _: &[(Operation, f64)]) -> Option<Vec<f64>> {
+            panic!("probabilities computed without a hook asking for them")
+        }
+    }
+
+    #[test]
+    fn skips_probabilities_unless_hooked() {
+        let (sender, receiver) = channel();
+        let table: Table<i8, Operation> = Table::default();
+        drop(receiver);
+
+        play_until(
+            &mut Counter::default(),
+            &mut Unasked,
+            &RwLock::new(table),
+            &sender,
+            &Control::default(),
+            &mut (),
+        );
+    }
+
+    #[test]
+    fn test_play_until_stopped() {
+        let (sender, receiver) = channel();
+        let control = Control::default();
+        let table: Table<i8, Operation> = Table::default();
+
+        control.stop();
+        play_until(
+            &mut Counter::default(),
+            &mut Greedy::default(),
+            &RwLock::new(table),
+            &sender,
+            &control,
+            &mut (),
+        );
+
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_play_until_bounded() {
+        let (sender, receiver) = sync_channel(1);
+        let table: Table<i8, Operation> = Table::default();
+
+        spawn(move || {
+            play_until(
+                &mut Counter::default(),
+                &mut Greedy::default(),
+                &RwLock::new(table),
+                &sender,
+                &Control::default(),
+                &mut (),
+            )
+        });
+
+        assert_eq!(receiver.recv().unwrap().0, 0);
+        assert_eq!(receiver.recv().unwrap().0.abs(), 1);
+    }
 }
@@ -0,0 +1,68 @@
+use {Brain, Game, Hook, Memory, Policy, ValueMemory};
+use super::step::{act, action_values, learn_value, Episodes};
+use super::Control;
+
+pub fn predict<G, P, M, V, H>(
+    game: &mut G,
+    policy: &mut P,
+    memory: &M,
+    values: &mut V,
+    brain: &Brain,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    P: Policy<G::State, G::Action>,
+    M: Memory<G::State, G::Action>,
+    V: ValueMemory<G::State>,
+    H: Hook<G::State, G::Action>,
+{
+    let mut episodes = Episodes::new();
+
+    while !control.stopped() {
+        let action_values = action_values(game, |state, action| memory.get(state, action));
+        let sample = match act(game, policy, action_values, hook) {
+            Some(sample) => sample,
+            None => break,
+        };
+        let reward = sample.3;
+        let finished = game.finished();
+
+        learn_value(values, brain, sample, finished, hook, control);
+        episodes.record(reward, finished, hook, control);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::{Table, ValueTable};
+    use policies::Greedy;
+    use tasks::Limits;
+
+    #[test]
+    fn evaluates_fixed_policy() {
+        let mut game = Counter::default();
+        let mut memory: Table<i8, Operation> = Table::default();
+        let mut values = ValueTable::default();
+        memory.set(0, Operation::Inc, 1.);
+        memory.set(1, Operation::Dec, 1.);
+
+        predict(
+            &mut game,
+            &mut Greedy,
+            &memory,
+            &mut values,
+            &Brain::new(0.5, 0.5),
+            &Control::new(Limits {
+                samples: Some(1000),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        assert!((values.get(&0) - 2. / 3.).abs() < 1e-6);
+        assert!((values.get(&1) + 2. / 3.).abs() < 1e-6);
+    }
+}
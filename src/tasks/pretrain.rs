@@ -0,0 +1,189 @@
+use {Brain, Game, Hook, Memory, Sample, Update};
+use super::Control;
+use policy::argmax;
+
+pub fn pretrain<G, M, H>(
+    game: &G,
+    memory: &mut M,
+    demonstrations: &[Sample<G::State, G::Action>],
+    brain: &Brain,
+    margin: f64,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone + PartialEq,
+    M: Memory<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    while !demonstrations.is_empty() && !control.stopped() {
+        for sample in demonstrations {
+            if control.stopped() {
+                return;
+            }
+
+            let value1 = best(game, memory, &sample.2, None)
+                .map_or(0., |(action, _)| memory.bootstrap(&sample.2, &action));
+            let value0 = memory.raw(&sample.0, &sample.1);
+            let learned = brain.learn(value0, value1, sample.3);
+
+            hook.update(&Update {
+                sample: sample,
+                error: brain.error(value0, value1, sample.3),
+                delta: learned - value0,
+            });
+            memory.set(sample.0.clone(), sample.1.clone(), learned);
+
+            let expert = memory.raw(&sample.0, &sample.1);
+            if let Some((action, value)) = best(game, memory, &sample.0, Some(&sample.1)) {
+                let violation = value + margin - expert;
+                if violation > 0. {
+                    let step = brain.alpha() * violation / 2.;
+                    memory.set(sample.0.clone(), action, value - step);
+                    memory.set(sample.0.clone(), sample.1.clone(), expert + step);
+                }
+            }
+
+            control.sample(learned - value0);
+        }
+
+        if !control.limited() {
+            return;
+        }
+    }
+}
+
+fn best<G, M>(
+    game: &G,
+    memory: &M,
+    state: &G::State,
+    except: Option<&G::Action>,
+) -> Option<(G::Action, f64)>
+where
+    G: Game,
+    G::Action: PartialEq,
+    M: Memory<G::State, G::Action>,
+{
+    let mut action_values: Vec<(G::Action, f64)> = game
+        .actions()
+        .into_iter()
+        .filter(|action| game.legal(state, action) && except != Some(action))
+        .map(|action| {
+            let value = memory.raw(state, &action);
+            (action, value)
+        })
+        .collect();
+
+    let index = argmax(action_values.iter().map(|&(_, value)| value))?;
+    Some(action_values.swap_remove(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+    use policies::{Greedy, Scripted};
+    use std::sync::mpsc::channel;
+    use std::sync::RwLock;
+    use std::thread;
+    use tasks::{play, walk, Limits};
+    use Policy;
+
+    #[test]
+    fn separates_expert_actions_by_margin() {
+        let mut memory = Table::default();
+
+        pretrain(
+            &Counter::default(),
+            &mut memory,
+            &[(0, Operation::Dec, -1, -1.)],
+            &Brain::new(1., 0.),
+            2.,
+            &Control::new(Limits {
+                samples: Some(1),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        assert_eq!(memory.get(&0, &Operation::Dec), 0.5);
+        assert_eq!(memory.get(&0, &Operation::Inc), -1.5);
+    }
+
+    #[test]
+    fn breaks_ties_like_greedy() {
+        let game = Counter::default();
+        let memory: Table<i8, Operation> = Table::default();
+        let action_values: Vec<_> = game.actions().into_iter().map(|a| (a, 0.)).collect();
+
+        let index = Greedy.choose(&0, &action_values).unwrap();
+        assert_eq!(best(&game, &memory, &0, None), Some(action_values[index]));
+    }
+
+    #[test]
+    fn single_pass_without_limits() {
+        let mut memory = Table::default();
+        let control = Control::default();
+
+        pretrain(
+            &Counter::default(),
+            &mut memory,
+            &[(0, Operation::Dec, -1, -1.), (-1, Operation::Inc, 0, 1.)],
+            &Brain::new(1., 0.),
+            0.,
+            &control,
+            &mut (),
+        );
+
+        assert_eq!(control.summary().samples, 2);
+        assert_eq!(memory.get(&-1, &Operation::Inc), 1.);
+    }
+
+    #[test]
+    fn imitates_scripted_demonstrations() {
+        let (sender, receiver) = channel();
+        let player = thread::spawn(move || {
+            let table: Table<i8, Operation> = Table::default();
+            let mut script = Scripted::new(|state: &i8| {
+                Some(if *state > 0 {
+                    Operation::Dec
+                } else {
+                    Operation::Inc
+                })
+            });
+            play(
+                &mut Counter::default(),
+                &mut script,
+                &RwLock::new(table),
+                &sender,
+            );
+        });
+
+        let demonstrations: Vec<_> = receiver.iter().take(10).collect();
+        drop(receiver);
+        player.join().unwrap();
+
+        let mut memory = Table::default();
+        pretrain(
+            &Counter::default(),
+            &mut memory,
+            &demonstrations,
+            &Brain::new(0.5, 0.5),
+            1.,
+            &Control::new(Limits {
+                samples: Some(200),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        let mut game = Counter::default();
+        let states: Vec<i8> = walk(&mut game, &mut Greedy, &memory)
+            .take(4)
+            .map(|(_, game)| game.current_value)
+            .collect();
+        assert_eq!(states, vec![1, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,70 @@
+use {Brain, Game, Policy, Replay, Sample, Shared};
+use std::sync::mpsc::Receiver;
+use super::train::learn;
+
+pub fn replay<G, P, C>(
+    game: &G,
+    policy: &mut P,
+    memory: &C,
+    receiver: &Receiver<Sample<G::State, G::Action>>,
+    brain: &Brain,
+    buffer: &mut Replay<G::State, G::Action>,
+    size: usize,
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+{
+    while let Ok(sample) = receiver.recv() {
+        buffer.push(sample);
+
+        for sample in buffer.batch(size) {
+            if learn(game, policy, memory, sample.clone(), brain, &mut ()).is_none() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use std::sync::mpsc::sync_channel;
+    use std::thread::{sleep, spawn};
+    use std::time::Duration;
+    use policies::Greedy;
+    use memories::Table;
+    use std::sync::{Arc, RwLock};
+    use Memory;
+
+    #[test]
+    fn test_replay() {
+        let (sender, receiver) = sync_channel(0);
+        let table: Table<i8, Operation> = Table::default();
+        let memory = Arc::new(RwLock::new(table));
+
+        let training_memory = memory.clone();
+        spawn(move || {
+            replay(
+                &Counter::default(),
+                &mut Greedy::default(),
+                &training_memory,
+                &receiver,
+                &Brain::new(0.5, 0.),
+                &mut Replay::new(1),
+                3,
+            )
+        });
+
+        sender.send((0, Operation::Inc, 1, 4.0)).unwrap();
+        sleep(Duration::from_millis(1));
+        assert_eq!(memory.read().unwrap().get(&0, &Operation::Inc), 3.5);
+        sender.send((1, Operation::Dec, 0, 4.0)).unwrap();
+        sleep(Duration::from_millis(1));
+        assert_eq!(memory.read().unwrap().get(&0, &Operation::Inc), 3.5);
+        assert_eq!(memory.read().unwrap().get(&1, &Operation::Dec), 3.5);
+    }
+}
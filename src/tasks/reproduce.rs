@@ -0,0 +1,69 @@
+use {Game, Trajectory};
+
+pub fn reproduce<G>(game: &mut G, trajectory: &Trajectory<G::State, G::Action>) -> Result<(), usize>
+where
+    G: Game,
+    G::State: PartialEq,
+{
+    let steps = trajectory.actions.len();
+    if trajectory.states.len() != steps + 1 || trajectory.rewards.len() != steps {
+        return Err(0);
+    }
+    if trajectory.states.first() != Some(&game.state()) {
+        return Err(0);
+    }
+
+    for (step, action) in trajectory.actions.iter().enumerate() {
+        game.act(action);
+
+        let reward = game.reward();
+        if reward != trajectory.rewards[step] || game.state() != trajectory.states[step + 1] {
+            return Err(step + 1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn reproduces_recorded_trajectory() {
+        let mut trajectory = Trajectory::new(0);
+        trajectory.push(Operation::Inc, 1., 1, None);
+        trajectory.push(Operation::Dec, -1., 0, None);
+
+        let mut game = Counter::default();
+        assert_eq!(reproduce(&mut game, &trajectory), Ok(()));
+        assert_eq!(game.state(), 0);
+    }
+
+    #[test]
+    fn reports_first_divergent_step() {
+        let mut trajectory = Trajectory::new(0);
+        trajectory.push(Operation::Inc, 1., 1, None);
+        trajectory.push(Operation::Inc, 1., 3, None);
+
+        assert_eq!(reproduce(&mut Counter::default(), &trajectory), Err(2));
+
+        let mut game = Counter {
+            last_value: 0,
+            current_value: 5,
+        };
+        assert_eq!(reproduce(&mut game, &trajectory), Err(0));
+    }
+
+    #[test]
+    fn rejects_inconsistent_trajectory() {
+        let mut trajectory = Trajectory::new(0);
+        trajectory.push(Operation::Inc, 1., 1, None);
+        trajectory.rewards.pop();
+        assert_eq!(reproduce(&mut Counter::default(), &trajectory), Err(0));
+
+        trajectory.rewards.push(1.);
+        trajectory.states.pop();
+        assert_eq!(reproduce(&mut Counter::default(), &trajectory), Err(0));
+    }
+}
@@ -0,0 +1,123 @@
+use {Brain, Game, Hook, Memory, Policy, Shared};
+use std::cell::RefCell;
+use super::step::{act, action_values, Episodes};
+use super::train::learn;
+use super::Control;
+
+pub fn run<G, P, Q, M, H>(
+    game: &mut G,
+    player: &mut P,
+    trainer: &mut Q,
+    memory: &mut M,
+    brain: &Brain,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    Q: Policy<G::State, G::Action>,
+    M: Memory<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    let memory = RefCell::new(memory);
+    let mut episodes = Episodes::new();
+
+    while !control.stopped() {
+        let action_values = action_values(game, |state, action| memory.get(state, action));
+        let sample = match act(game, player, action_values, hook) {
+            Some(sample) => sample,
+            None => break,
+        };
+        let finished = game.finished();
+
+        episodes.record(sample.3, finished, hook, control);
+
+        match learn(game, trainer, &memory, sample, brain, hook) {
+            Some(delta) => control.sample(delta),
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use memories::Table;
+    use hooks::Throughput;
+    use policies::{Egreedy, Greedy};
+    use rand::{SeedableRng, StdRng};
+    use tasks::Limits;
+
+    fn learned(seed: usize) -> Vec<f64> {
+        let mut table: Table<i8, Operation> = Table::default();
+
+        run(
+            &mut Counter::default(),
+            &mut Egreedy::with_rng(0.5, StdRng::from_seed(&[seed])),
+            &mut Greedy::default(),
+            &mut table,
+            &Brain::new(0.5, 0.5),
+            &Control::new(Limits {
+                samples: Some(1000),
+                ..Limits::default()
+            }),
+            &mut (),
+        );
+
+        (-10..10)
+            .flat_map(|s| vec![table.get(&s, &Operation::Dec), table.get(&s, &Operation::Inc)])
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_memory() {
+        assert_eq!(learned(1), learned(1));
+        assert!(learned(1) != learned(2));
+    }
+
+    #[test]
+    fn learns_each_step() {
+        let mut table: Table<i8, Operation> = Table::default();
+        let control = Control::new(Limits {
+            samples: Some(1),
+            ..Limits::default()
+        });
+
+        run(
+            &mut Counter::default(),
+            &mut Greedy::default(),
+            &mut Greedy::default(),
+            &mut table,
+            &Brain::new(0.5, 0.5),
+            &control,
+            &mut (),
+        );
+
+        assert_eq!(control.summary().samples, 1);
+        assert_eq!(table.get(&0, &Operation::Inc), 0.5);
+    }
+
+    #[test]
+    fn reports_updates_to_hook() {
+        let mut table: Table<i8, Operation> = Table::default();
+        let mut throughput = Throughput::default();
+
+        run(
+            &mut Counter::default(),
+            &mut Greedy::default(),
+            &mut Greedy::default(),
+            &mut table,
+            &Brain::new(0.5, 0.5),
+            &Control::new(Limits {
+                samples: Some(10),
+                ..Limits::default()
+            }),
+            &mut throughput,
+        );
+
+        assert_eq!(throughput.updates(), 10);
+    }
+}
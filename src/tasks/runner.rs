@@ -0,0 +1,205 @@
+use {Brain, Game, Hook, Policy, Shared};
+use std::panic::resume_unwind;
+use std::sync::mpsc::sync_channel;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use super::{play_until, train_until, Control, Limits, Summary};
+
+const QUEUE: usize = 1024;
+
+pub struct Finished<C, H, K> {
+    pub memory: C,
+    pub players: Vec<H>,
+    pub trainer: K,
+    pub summary: Summary,
+}
+
+pub struct Runner<C, H, K> {
+    memory: Arc<C>,
+    control: Arc<Control>,
+    players: Vec<JoinHandle<H>>,
+    trainer: JoinHandle<K>,
+}
+
+impl<C, H, K> Runner<C, H, K> {
+    pub fn memory(&self) -> &C {
+        &self.memory
+    }
+
+    pub fn summary(&self) -> Summary {
+        self.control.summary()
+    }
+
+    pub fn stop(self) -> Finished<C, H, K> {
+        self.control.stop();
+        self.wait()
+    }
+
+    pub fn wait(self) -> Finished<C, H, K> {
+        let players = self.players
+            .into_iter()
+            .map(|player| match player.join() {
+                Ok(hook) => hook,
+                Err(panic) => resume_unwind(panic),
+            })
+            .collect();
+        let trainer = match self.trainer.join() {
+            Ok(hook) => hook,
+            Err(panic) => resume_unwind(panic),
+        };
+
+        match Arc::try_unwrap(self.memory) {
+            Ok(memory) => Finished {
+                memory: memory,
+                players: players,
+                trainer: trainer,
+                summary: self.control.summary(),
+            },
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+pub fn spawn<G, P, Q, H, K, F, T, C>(
+    game: G,
+    players: usize,
+    player: F,
+    trainer: T,
+    memory: C,
+    brain: Brain,
+    limits: Limits,
+) -> Runner<C, H, K>
+where
+    G: Game + Clone + Send + 'static,
+    G::State: Clone + Send + 'static,
+    G::Action: Clone + Send + 'static,
+    P: Policy<G::State, G::Action>,
+    Q: Policy<G::State, G::Action>,
+    H: Hook<G::State, G::Action> + Send + 'static,
+    K: Hook<G::State, G::Action> + Send + 'static,
+    F: Fn(usize) -> (P, H) + Send + Sync + 'static,
+    T: FnOnce() -> (Q, K) + Send + 'static,
+    C: Shared<G::State, G::Action> + Send + Sync + 'static,
+{
+    let (sender, receiver) = sync_channel(QUEUE);
+    let memory = Arc::new(memory);
+    let control = Arc::new(Control::new(limits));
+    let player = Arc::new(player);
+
+    let players = (0..players)
+        .map(|index| {
+            let mut game = game.clone();
+            let sender = sender.clone();
+            let memory = memory.clone();
+            let control = control.clone();
+            let player = player.clone();
+
+            thread::spawn(move || {
+                let (mut policy, mut hook) = player(index);
+                play_until(
+                    &mut game,
+                    &mut policy,
+                    &*memory,
+                    &sender,
+                    &control,
+                    &mut hook,
+                );
+                hook
+            })
+        })
+        .collect();
+
+    let trainer = {
+        let memory = memory.clone();
+        let control = control.clone();
+        thread::spawn(move || {
+            let (mut policy, mut hook) = trainer();
+            train_until(
+                &game,
+                &mut policy,
+                &*memory,
+                &receiver,
+                &brain,
+                &control,
+                &mut hook,
+            );
+            hook
+        })
+    };
+
+    Runner {
+        memory: memory,
+        control: control,
+        players: players,
+        trainer: trainer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+    use hooks::{Recorder, Throughput};
+    use memories::{Sharded, Table};
+    use policies::{Egreedy, Greedy};
+    use std::sync::RwLock;
+    use Memory;
+
+    #[test]
+    fn players_feed_one_trainer() {
+        let runner = spawn(
+            Counter::default(),
+            4,
+            |index| (Egreedy::new(0.1 * index as f64), ()),
+            || (Greedy::default(), ()),
+            Sharded::new(4),
+            Brain::new(0.5, 0.5),
+            Limits {
+                samples: Some(1000),
+                ..Limits::default()
+            },
+        );
+        let finished = runner.wait();
+
+        assert_eq!(finished.summary.samples, 1000);
+        assert!(Memory::get(&finished.memory, &0, &Operation::Inc) != 0.);
+    }
+
+    #[test]
+    fn stops_with_rwlock_memory() {
+        let table: Table<i8, Operation> = Table::default();
+        let runner = spawn(
+            Counter::default(),
+            2,
+            |_| (Greedy::default(), ()),
+            || (Greedy::default(), ()),
+            RwLock::new(table),
+            Brain::new(0.5, 0.5),
+            Limits::default(),
+        );
+
+        runner.stop().memory.into_inner().unwrap();
+    }
+
+    #[test]
+    fn returns_hooks() {
+        let runner = spawn(
+            Counter::default(),
+            2,
+            |_| (Egreedy::new(0.5), Recorder::new(vec![])),
+            || (Greedy::default(), Throughput::default()),
+            Sharded::new(4),
+            Brain::new(0.5, 0.5),
+            Limits {
+                samples: Some(100),
+                ..Limits::default()
+            },
+        );
+
+        let finished = runner.wait();
+        assert_eq!(finished.summary.samples, 100);
+        assert_eq!(finished.players.len(), 2);
+        assert!(finished.players.iter().map(|hook| hook.samples()).sum::<usize>() >= 100);
+        assert_eq!(finished.trainer.updates(), 100);
+    }
+}
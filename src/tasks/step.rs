@@ -0,0 +1,111 @@
+use {Brain, Episode, Game, Hook, Policy, Sample, Step, Update, ValueMemory};
+use super::Control;
+
+pub fn action_values<G, F>(game: &G, value: F) -> Vec<(G::Action, f64)>
+where
+    G: Game,
+    F: Fn(&G::State, &G::Action) -> f64,
+{
+    let state = game.state();
+    let mut action_values = vec![];
+
+    for action in game.actions() {
+        if !game.legal(&state, &action) {
+            continue;
+        }
+        let value = value(&state, &action);
+        action_values.push((action, value));
+    }
+    action_values
+}
+
+pub fn act<G, P, H>(
+    game: &mut G,
+    policy: &mut P,
+    mut action_values: Vec<(G::Action, f64)>,
+    hook: &mut H,
+) -> Option<Sample<G::State, G::Action>>
+where
+    G: Game,
+    P: Policy<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    let state = game.state();
+    let probabilities = if hook.probabilities() {
+        policy.probabilities(&state, &action_values)
+    } else {
+        None
+    };
+    let epsilon = policy.epsilon(&state);
+    let index = policy.choose(&state, &action_values)?;
+    let action = action_values.swap_remove(index).0;
+
+    game.act(&action);
+    let reward = game.reward();
+    let sample = (state, action, game.state(), reward);
+
+    hook.step(&Step {
+        sample: &sample,
+        epsilon: epsilon,
+        probability: probabilities.and_then(|probabilities| probabilities.get(index).cloned()),
+    });
+    Some(sample)
+}
+
+pub fn learn_value<S, A, V, H>(
+    values: &mut V,
+    brain: &Brain,
+    sample: Sample<S, A>,
+    finished: bool,
+    hook: &mut H,
+    control: &Control,
+) where
+    V: ValueMemory<S>,
+    H: Hook<S, A>,
+{
+    let value0 = values.get(&sample.0);
+    let value1 = if finished { 0. } else { values.get(&sample.2) };
+    let learned = brain.learn(value0, value1, sample.3);
+
+    hook.update(&Update {
+        sample: &sample,
+        error: brain.error(value0, value1, sample.3),
+        delta: learned - value0,
+    });
+
+    values.set(sample.0, learned);
+    control.sample(learned - value0);
+}
+
+pub struct Episodes {
+    current: Episode,
+}
+
+impl Episodes {
+    pub fn new() -> Episodes {
+        Episodes {
+            current: Episode {
+                length: 0,
+                reward: 0.,
+            },
+        }
+    }
+
+    pub fn record<S, A, H: Hook<S, A>>(
+        &mut self,
+        reward: f64,
+        finished: bool,
+        hook: &mut H,
+        control: &Control,
+    ) {
+        self.current.length += 1;
+        self.current.reward += reward;
+
+        if finished {
+            hook.episode(&self.current);
+            control.episode();
+            self.current.length = 0;
+            self.current.reward = 0.;
+        }
+    }
+}
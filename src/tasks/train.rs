@@ -1,34 +1,100 @@
-use {Brain, Game, Memory, Policy, Sample};
+use {Brain, Game, Hook, Policy, Sample, Shared, Update};
 use std::sync::mpsc::Receiver;
-use std::sync::RwLock;
+use super::Control;
 
-pub fn train<G: Game, P: Policy, M: Memory<G::State, G::Action>>(
+pub fn train<G, P, C>(
     game: &G,
     policy: &mut P,
-    memory: &RwLock<M>,
+    memory: &C,
     receiver: &Receiver<Sample<G::State, G::Action>>,
     brain: &Brain,
-) {
-    while let Ok((state0, action0, state1, reward)) = receiver.recv() {
-        let mut memory = match memory.write() {
-            Ok(memory) => memory,
-            Err(_) => break,
-        };
-
-        let mut action_values = vec![];
-
-        for action in game.actions() {
-            let value = memory.get(&state1, &action);
-            action_values.push((action, value));
-        }
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+{
+    train_until(
+        game,
+        policy,
+        memory,
+        receiver,
+        brain,
+        &Control::default(),
+        &mut (),
+    )
+}
 
-        let action1 = policy.choose(action_values).unwrap();
+pub fn train_until<G, P, C, H>(
+    game: &G,
+    policy: &mut P,
+    memory: &C,
+    receiver: &Receiver<Sample<G::State, G::Action>>,
+    brain: &Brain,
+    control: &Control,
+    hook: &mut H,
+) where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    while let Ok(sample) = receiver.recv() {
+        if control.stopped() {
+            break;
+        }
+        match learn(game, policy, memory, sample, brain, hook) {
+            Some(delta) => control.sample(delta),
+            None => break,
+        }
+    }
+}
 
-        let value0 = memory.get(&state0, &action0);
-        let value1 = memory.get(&state1, &action1);
+pub fn learn<G, P, C, H>(
+    game: &G,
+    policy: &mut P,
+    memory: &C,
+    sample: Sample<G::State, G::Action>,
+    brain: &Brain,
+    hook: &mut H,
+) -> Option<f64>
+where
+    G: Game,
+    G::State: Clone,
+    G::Action: Clone,
+    P: Policy<G::State, G::Action>,
+    C: Shared<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
+    let mut action_values = vec![];
 
-        memory.set(state0, action0, brain.learn(value0, value1, reward));
+    for action in game.actions() {
+        if !game.legal(&sample.2, &action) {
+            continue;
+        }
+        let value = memory.raw(&sample.2, &action);
+        action_values.push((action, value));
     }
+
+    let value1 = match policy.choose(&sample.2, &action_values) {
+        Some(index) => memory.bootstrap(&sample.2, &action_values[index].0),
+        None => 0.,
+    };
+
+    let value0 = memory.update(sample.0.clone(), sample.1.clone(), |value0| {
+        brain.learn(value0, value1, sample.3)
+    })?;
+    let learned = brain.learn(value0, value1, sample.3);
+
+    hook.update(&Update {
+        sample: &sample,
+        error: brain.error(value0, value1, sample.3),
+        delta: learned - value0,
+    });
+    Some(learned - value0)
 }
 
 #[cfg(test)]
@@ -39,8 +105,10 @@ mod tests {
     use std::thread::{sleep, spawn};
     use std::time::Duration;
     use policies::Greedy;
-    use memories::Table;
-    use std::sync::Arc;
+    use memories::{Sharded, Table};
+    use std::cell::RefCell;
+    use std::sync::{Arc, RwLock};
+    use Memory;
 
     #[test]
     fn test_train() {
@@ -74,4 +142,69 @@ mod tests {
         assert_eq!(memory.read().unwrap().get(&0, &Operation::Inc), 3.625);
         assert_eq!(memory.read().unwrap().get(&1, &Operation::Dec), 2.5);
     }
+
+    #[test]
+    fn test_train_sharded() {
+        let (sender, receiver) = sync_channel(0);
+        let memory = Arc::new(Sharded::new(4));
+
+        let training_memory = memory.clone();
+        spawn(move || {
+            train(
+                &Counter::default(),
+                &mut Greedy::default(),
+                &training_memory,
+                &receiver,
+                &Brain::new(0.5, 0.5),
+            )
+        });
+
+        sender.send((0, Operation::Inc, 1, 4.0)).unwrap();
+        sleep(Duration::from_millis(1));
+        assert_eq!(Shared::get(&memory, &0, &Operation::Inc), 2.0);
+        sender.send((1, Operation::Dec, 0, 4.0)).unwrap();
+        sleep(Duration::from_millis(1));
+        assert_eq!(Shared::get(&memory, &1, &Operation::Dec), 2.5);
+    }
+
+    #[test]
+    fn stops_on_poisoned_memory() {
+        let (sender, receiver) = sync_channel(1);
+        let table: Table<i8, Operation> = Table::default();
+        let memory = Arc::new(RwLock::new(table));
+
+        let poisoning_memory = memory.clone();
+        spawn(move || {
+            let _guard = poisoning_memory.write().unwrap();
+            panic!("poisoned");
+        }).join()
+            .unwrap_err();
+
+        sender.send((0, Operation::Inc, 1, 4.0)).unwrap();
+        train(
+            &Counter::default(),
+            &mut Greedy::default(),
+            &memory,
+            &receiver,
+            &Brain::new(0.5, 0.5),
+        );
+        assert!(memory.is_poisoned());
+    }
+
+    #[test]
+    fn test_learn_legal_actions() {
+        let mut memory: Table<i8, Operation> = Table::default();
+        memory.set(1, Operation::Inc, 8.);
+        memory.set(1, Operation::Dec, 2.);
+
+        learn(
+            &Capped::default(),
+            &mut Greedy,
+            &RefCell::new(&mut memory),
+            (0, Operation::Inc, 1, 0.),
+            &Brain::new(1., 0.5),
+            &mut (),
+        );
+        assert_eq!(memory.get(&0, &Operation::Inc), 1.);
+    }
 }
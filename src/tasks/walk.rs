@@ -1,42 +1,77 @@
-use {Game, Memory, Policy};
+use {Episode, Game, Hook, Memory, Policy};
+use super::step::{act, action_values};
 
-pub struct Walk<'a, G: 'a + Game, M: 'a + Memory<G::State, G::Action>, P: 'a + Policy> {
+pub struct Walk<'a, G, M, P, H>
+where
+    G: 'a + Game,
+    M: 'a + Memory<G::State, G::Action>,
+    P: 'a + Policy<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
     game: &'a mut G,
     policy: &'a mut P,
     memory: &'a M,
+    hook: H,
+    episode: Episode,
 }
 
-impl<'a, G: Game + Clone, M: Memory<G::State, G::Action>, P: Policy> Iterator
-    for Walk<'a, G, M, P> {
+impl<'a, G, M, P, H> Iterator for Walk<'a, G, M, P, H>
+where
+    G: Game + Clone,
+    M: Memory<G::State, G::Action>,
+    P: Policy<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
     type Item = (G::Action, G);
 
     fn next(&mut self) -> Option<(G::Action, G)> {
-        let state = self.game.state();
+        let memory = self.memory;
+        let action_values = action_values(&*self.game, |state, action| memory.get(state, action));
+        let sample = act(self.game, self.policy, action_values, &mut self.hook)?;
 
-        let mut action_values = vec![];
+        self.episode.length += 1;
+        self.episode.reward += sample.3;
 
-        for action in self.game.actions() {
-            let value = self.memory.get(&state, &action);
-            action_values.push((action, value));
+        if self.game.finished() {
+            self.hook.episode(&self.episode);
+            self.episode.length = 0;
+            self.episode.reward = 0.;
         }
 
-        if let Some(action) = self.policy.choose(action_values) {
-            self.game.act(&action);
-            return Some((action, self.game.clone()));
-        }
-        None
+        Some((sample.1, self.game.clone()))
     }
 }
 
-pub fn walk<'a, G: Game, M: Memory<G::State, G::Action>, P: Policy>(
+pub fn walk<'a, G, M, P>(game: &'a mut G, policy: &'a mut P, memory: &'a M) -> Walk<'a, G, M, P, ()>
+where
+    G: Game,
+    M: Memory<G::State, G::Action>,
+    P: Policy<G::State, G::Action>,
+{
+    walk_with(game, policy, memory, ())
+}
+
+pub fn walk_with<'a, G, M, P, H>(
     game: &'a mut G,
     policy: &'a mut P,
     memory: &'a M,
-) -> Walk<'a, G, M, P> {
+    hook: H,
+) -> Walk<'a, G, M, P, H>
+where
+    G: Game,
+    M: Memory<G::State, G::Action>,
+    P: Policy<G::State, G::Action>,
+    H: Hook<G::State, G::Action>,
+{
     Walk {
         game: game,
         memory: memory,
         policy: policy,
+        hook: hook,
+        episode: Episode {
+            length: 0,
+            reward: 0.,
+        },
     }
 }
 
@@ -47,6 +82,19 @@ mod tests {
     use memories::Table;
     use policies::Greedy;
 
+    struct Parity;
+
+    impl Policy<i8, Operation> for Parity {
+        fn choose(&mut self, state: &i8, action_values: &[(Operation, f64)]) -> Option<usize> {
+            let wanted = if state % 2 == 0 {
+                Operation::Inc
+            } else {
+                Operation::Dec
+            };
+            action_values.iter().position(|&(action, _)| action == wanted)
+        }
+    }
+
     #[test]
     fn test_walk() {
         let mut game = Counter::default();
@@ -77,4 +125,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_walk_state_aware() {
+        let mut game = Counter::default();
+        let memory: Table<i8, Operation> = Table::default();
+
+        let states: Vec<i8> = walk(&mut game, &mut Parity, &memory)
+            .take(4)
+            .map(|(_, game)| game.current_value)
+            .collect();
+
+        assert_eq!(states, vec![1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_walk_legal_actions() {
+        let mut game = Capped {
+            cap: 1,
+            ..Capped::default()
+        };
+        let mut memory: Table<i8, Operation> = Table::default();
+        memory.set(0, Operation::Inc, 1.);
+        memory.set(1, Operation::Inc, 1.);
+
+        let states: Vec<i8> = walk(&mut game, &mut Greedy, &memory)
+            .take(4)
+            .map(|(_, game)| game.state())
+            .collect();
+
+        assert_eq!(states, vec![1, 0, 1, 0]);
+    }
 }
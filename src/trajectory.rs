@@ -0,0 +1,97 @@
+use Codec;
+use codec::invalid;
+use std::io::{self, Read, Write};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory<S, A> {
+    pub states: Vec<S>,
+    pub actions: Vec<A>,
+    pub rewards: Vec<f64>,
+    pub probabilities: Vec<Option<f64>>,
+}
+
+impl<S, A> Trajectory<S, A> {
+    pub fn new(state: S) -> Trajectory<S, A> {
+        Trajectory {
+            states: vec![state],
+            actions: vec![],
+            rewards: vec![],
+            probabilities: vec![],
+        }
+    }
+
+    pub fn push(&mut self, action: A, reward: f64, state: S, probability: Option<f64>) {
+        self.actions.push(action);
+        self.rewards.push(reward);
+        self.states.push(state);
+        self.probabilities.push(probability);
+    }
+
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    pub fn reward(&self) -> f64 {
+        self.rewards.iter().sum()
+    }
+}
+
+impl<S: Codec, A: Codec> Codec for Trajectory<S, A> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.states.write_to(writer)?;
+        self.actions.write_to(writer)?;
+        self.rewards.write_to(writer)?;
+        self.probabilities.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Trajectory<S, A>> {
+        let trajectory = Trajectory {
+            states: Codec::read_from(reader)?,
+            actions: Codec::read_from(reader)?,
+            rewards: Codec::read_from(reader)?,
+            probabilities: Codec::read_from(reader)?,
+        };
+
+        let steps = trajectory.actions.len();
+        if trajectory.states.len() != steps + 1
+            || trajectory.rewards.len() != steps
+            || trajectory.probabilities.len() != steps
+        {
+            return Err(invalid("trajectory"));
+        }
+        Ok(trajectory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game::counter::*;
+
+    #[test]
+    fn pushes_steps() {
+        let mut trajectory = Trajectory::new(0);
+        assert!(trajectory.is_empty());
+
+        trajectory.push(Operation::Inc, 1., 1, Some(0.5));
+        trajectory.push(Operation::Dec, -1., 0, None);
+
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory.reward(), 0.);
+        assert_eq!(trajectory.states, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn codec_roundtrip() {
+        let mut trajectory = Trajectory::new(0i8);
+        trajectory.push(Operation::Inc, 1., 1, Some(0.5));
+
+        let mut bytes = vec![];
+        trajectory.write_to(&mut bytes).unwrap();
+        assert_eq!(Trajectory::read_from(&mut &bytes[..]).unwrap(), trajectory);
+    }
+}
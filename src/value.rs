@@ -0,0 +1,4 @@
+pub trait ValueMemory<S> {
+    fn get(&self, &S) -> f64;
+    fn set(&mut self, S, f64);
+}
@@ -1,15 +1,17 @@
 extern crate marskov;
+extern crate rand;
 
 use std::sync::Arc;
 use std::ops::Deref;
 use std::sync::RwLock;
 use std::time::Duration;
 use std::sync::mpsc::channel;
+use rand::{SeedableRng, StdRng};
 use std::thread::{sleep, spawn};
 
 use marskov::{Brain, Game};
 use marskov::memories::Table;
-use marskov::tasks::{play, train, walk};
+use marskov::tasks::{play, run, train, walk, Control, Limits};
 use marskov::policies::{Greedy, Random};
 
 #[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
@@ -149,3 +151,40 @@ fn solves_maze() {
         }
     }
 }
+
+#[test]
+fn solves_maze_deterministically() {
+    let mut table: Table<Maze, Move> = Table::default();
+
+    run(
+        &mut Maze::default(),
+        &mut Random::with_rng(StdRng::from_seed(&[1])),
+        &mut Greedy::default(),
+        &mut table,
+        &Brain::new(0.5, 0.5),
+        &Control::new(Limits {
+            samples: Some(5000),
+            ..Limits::default()
+        }),
+        &mut (),
+    );
+
+    let mut greedy = Greedy::default();
+    let mut maze = Maze::default();
+
+    let mut actions = vec![
+        Move::Up,
+        Move::Up,
+        Move::Right,
+        Move::Right,
+        Move::Down,
+        Move::Down,
+    ];
+
+    for (action, _) in walk(&mut maze, &mut greedy, &table) {
+        match actions.pop() {
+            Some(a) => assert_eq!(a, action),
+            None => break,
+        }
+    }
+}
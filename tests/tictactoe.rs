@@ -1,6 +1,7 @@
 extern crate marskov;
 extern crate rand;
 
+use std::io;
 use std::sync::Arc;
 use std::ops::Deref;
 use std::sync::RwLock;
@@ -9,9 +10,12 @@ use rand::{thread_rng, Rng};
 use std::sync::mpsc::channel;
 use std::thread::{sleep, spawn};
 
-use marskov::{Brain, Game};
+use std::io::{Read, Write};
+
+use marskov::{Brain, Codec, Game, Stream, Trajectory};
+use marskov::hooks::{Lengths, Trajectories};
 use marskov::memories::Table;
-use marskov::tasks::{play, train, walk};
+use marskov::tasks::{play, run, train, walk, walk_with, Control, Limits};
 use marskov::policies::{Egreedy, Greedy};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -27,6 +31,21 @@ impl Default for Tile {
     }
 }
 
+impl Codec for Tile {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as i8).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Tile> {
+        match i8::read_from(reader)? {
+            -1 => Ok(Tile::Janete),
+            0 => Ok(Tile::Empty),
+            1 => Ok(Tile::Robson),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid tile")),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 struct Board {
     tiles: [Tile; 9],
@@ -51,6 +70,14 @@ impl Game for Board {
         }
     }
 
+    fn finished(&self) -> bool {
+        self.invalid || self.winner != Tile::Empty || self.count == 9
+    }
+
+    fn legal(&self, board: &Board, m: &i8) -> bool {
+        *m == -1 || board.tiles[*m as usize] == Tile::Empty
+    }
+
     fn act(&mut self, m: &i8) {
         if *m == -1 {
             self.tiles = [Tile::Empty; 9];
@@ -131,6 +158,79 @@ impl Game for Board {
     }
 }
 
+impl Codec for Board {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.tiles.write_to(writer)?;
+        (self.invalid, self.winner, self.count).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Board> {
+        let tiles = Codec::read_from(reader)?;
+        let (invalid, winner, count) = Codec::read_from(reader)?;
+        Ok(Board {
+            tiles: tiles,
+            invalid: invalid,
+            winner: winner,
+            count: count,
+        })
+    }
+}
+
+#[test]
+fn records_walked_trajectories() {
+    let table: Table<Board, i8> = Table::default();
+    let mut trajectories = Trajectories::new(vec![]);
+
+    let steps = walk_with(
+        &mut Board::default(),
+        &mut Egreedy::new(0.1),
+        &table,
+        &mut trajectories,
+    ).take(20)
+        .count();
+    assert_eq!(steps, 20);
+
+    let episodes = trajectories.episodes();
+    let bytes = trajectories.finish().unwrap();
+    let read: Vec<Trajectory<Board, i8>> = Stream::new(&bytes[..])
+        .collect::<io::Result<_>>()
+        .unwrap();
+
+    assert!(episodes >= 2);
+    assert!(read.len() == episodes || read.len() == episodes + 1);
+    assert_eq!(read.iter().map(|t| t.actions.len()).sum::<usize>(), 20);
+    assert!(
+        read.iter()
+            .flat_map(|t| t.probabilities.iter())
+            .all(|p| p.map(|p| p > 0.).unwrap_or(false))
+    );
+}
+
+#[test]
+fn stops_after_episodes() {
+    let mut table: Table<Board, i8> = Table::default();
+    let mut lengths = Lengths::new(10);
+    let control = Control::new(Limits {
+        episodes: Some(10),
+        ..Limits::default()
+    });
+
+    run(
+        &mut Board::default(),
+        &mut Egreedy::new(0.1),
+        &mut Greedy::default(),
+        &mut table,
+        &Brain::new(0.5, 0.5),
+        &control,
+        &mut lengths,
+    );
+
+    let summary = control.summary();
+    assert_eq!(summary.episodes, 10);
+    assert!(summary.samples >= 10 * 5 && summary.samples <= 10 * 6);
+    assert!(lengths.average().unwrap() >= 5.);
+}
+
 #[test]
 fn solves_tictactoe() {
     let (sender, receiver) = channel();